@@ -9,16 +9,21 @@ use ndarray_rand::{RandomExt, rand_distr::StandardNormal};
 use ndarray_stats::{QuantileExt};
 
 
+fn random_walk_2d(size: usize) -> (Array2<f64>, f64) {
+    let mut y: Array2<f64> = Array2::random((size, 1), StandardNormal);
+    y.accumulate_axis_inplace(Axis(1), |&prev, curr| *curr += prev);
+    let max = y.max().unwrap().max(y.min().unwrap().abs());
+    let epsilon = max / 2000.0;
+    let x = Array1::linspace(0.0, max, size).insert_axis(Axis(1));
+    (concatenate![Axis(1), x, y], epsilon)
+}
+
+
 fn rdp_benches(c: &mut Criterion) {
     let mut group = c.benchmark_group("rdp_2d");
     for size in [1000, 10000, 100000, 1000000].iter() {
         group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, &size| {
-            let mut y = Array2::random((size, 1), StandardNormal);
-            y.accumulate_axis_inplace(Axis(1), |&prev, curr| *curr += prev);
-            let max = (*y.max().unwrap() as f64).max((*y.min().unwrap() as f64).abs());
-            let epsilon = max / 2000.0;
-            let x = Array1::linspace(0.0, max, size).insert_axis(Axis(1));
-            let points = concatenate![Axis(1), x, y];
+            let (points, epsilon) = random_walk_2d(size);
 
             b.iter(|| {
                 curved::rdp(points.view(), epsilon)
@@ -27,6 +32,30 @@ fn rdp_benches(c: &mut Criterion) {
     }
     group.finish();
 
+    let mut group = c.benchmark_group("reumann_witkam_2d");
+    for size in [1000, 10000, 100000, 1000000].iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, &size| {
+            let (points, epsilon) = random_walk_2d(size);
+
+            b.iter(|| {
+                curved::reumann_witkam(points.view(), epsilon)
+            });
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("opheim_2d");
+    for size in [1000, 10000, 100000, 1000000].iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, &size| {
+            let (points, epsilon) = random_walk_2d(size);
+
+            b.iter(|| {
+                curved::opheim(points.view(), epsilon, 100.0 * epsilon)
+            });
+        });
+    }
+    group.finish();
+
     c.bench_function("rdp_large_3d", |b| {
         let points = concatenate![
             Axis(1),
@@ -48,9 +77,45 @@ fn rdp_benches(c: &mut Criterion) {
     });
 }
 
+#[cfg(feature = "rayon")]
+fn par_rdp_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rdp_2d_parallel");
+    for size in [10000, 100000, 1000000, 10000000].iter() {
+        let (points, epsilon) = random_walk_2d(*size);
+        group.bench_with_input(BenchmarkId::new("serial", size), size, |b, _| {
+            b.iter(|| {
+                curved::rdp(points.view(), epsilon)
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", size), size, |b, _| {
+            b.iter(|| {
+                curved::par_rdp(points.view(), epsilon)
+            });
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("rdp_norway_parallel");
+    let points = include!("../fixtures/norway_main.rs");
+    group.bench_function("serial", |b| {
+        b.iter(|| {
+            curved::rdp(points.view(), 0.0005);
+        });
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| {
+            curved::par_rdp(points.view(), 0.0005);
+        });
+    });
+    group.finish();
+}
+
+#[cfg(not(feature = "rayon"))]
+fn par_rdp_benches(_c: &mut Criterion) {}
+
 criterion_group!{
     name = benches;
     config = Criterion::default().with_profiler(PProfProfiler::new(100, Output::Flamegraph(None)));
-    targets = rdp_benches
+    targets = rdp_benches, par_rdp_benches
 }
 criterion_main!(benches);
\ No newline at end of file
@@ -0,0 +1,66 @@
+use ndarray::{s, Axis, Array1, ArrayView2};
+
+use crate::{check_options, check_points, rdp_with_options, CurveFloat, CurvedError, RdpOptions};
+
+
+/// Simplifies many curves at once using the Ramer-Douglas-Peucker algorithm.
+///
+/// The curves are concatenated in `points`, with curve `i` spanning the points from
+/// `offsets[i]` up to `offsets[i + 1]`, as in GeoArrow. Each curve is simplified independently
+/// and the masks are concatenated in the same way. With the `rayon` feature and the `parallel`
+/// option set, the curves are simplified in parallel.
+///
+/// Panics if the offsets are invalid; see [`try_rdp_batch`] for a version that validates its
+/// inputs.
+pub fn rdp_batch<F: CurveFloat>(points: ArrayView2<'_, F>, offsets: &[usize], epsilon: F, options: &RdpOptions) -> Array1<bool> {
+    let mut mask = Array1::from_elem((points.len_of(Axis(0)),), false);
+    let parts: Vec<(usize, usize)> = offsets.windows(2)
+        .map(|part| (part[0], part[1]))
+        .filter(|(start, end)| end > start)
+        .collect();
+
+    #[cfg(feature = "rayon")]
+    if options.parallel {
+        use rayon::prelude::*;
+
+        let masks: Vec<Array1<bool>> = parts.par_iter()
+            .map(|&(start, end)| rdp_with_options(points.slice(s![start..end, ..]), epsilon, options))
+            .collect();
+        for (&(start, end), part_mask) in parts.iter().zip(masks) {
+            mask.slice_mut(s![start..end]).assign(&part_mask);
+        }
+        return mask;
+    }
+
+    for (start, end) in parts {
+        let part_mask = rdp_with_options(points.slice(s![start..end, ..]), epsilon, options);
+        mask.slice_mut(s![start..end]).assign(&part_mask);
+    }
+
+    mask
+}
+
+
+/// Simplifies many curves at once using the Ramer-Douglas-Peucker algorithm, after validating
+/// the inputs. See [`rdp_batch`].
+pub fn try_rdp_batch<F: CurveFloat>(points: ArrayView2<'_, F>, offsets: &[usize], epsilon: F, options: &RdpOptions) -> Result<Array1<bool>, CurvedError> {
+    check_offsets(offsets, points.len_of(Axis(0)))?;
+    if points.len_of(Axis(0)) > 0 {
+        check_points(points)?;
+    }
+    check_options(points, epsilon, options)?;
+
+    Ok(rdp_batch(points, offsets, epsilon, options))
+}
+
+
+pub(crate) fn check_offsets(offsets: &[usize], len: usize) -> Result<(), CurvedError> {
+    let valid = offsets.first() == Some(&0)
+        && offsets.last() == Some(&len)
+        && offsets.windows(2).all(|part| part[0] <= part[1]);
+    if valid {
+        Ok(())
+    } else {
+        Err(CurvedError::InvalidOffsets)
+    }
+}
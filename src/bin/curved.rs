@@ -0,0 +1,362 @@
+//! The `curved` command, which simplifies a curve read from a file or standard input.
+//!
+//! Points are read as CSV, whitespace separated text or a GeoJSON LineString, and either the
+//! retained points or their indices are written to standard output. Rows of text are written out
+//! unchanged, so columns beyond the dimensions simplified are passed through.
+
+use std::fs;
+use std::io::{self, BufWriter, Read, Write};
+use std::process;
+
+use clap::{crate_version, App, Arg, ArgMatches};
+use ndarray::{Array1, Array2, ArrayView2};
+use serde_json::Value;
+
+use curved::CurvedError;
+
+
+/// The ways the command can fail, which exit with different codes.
+enum Error {
+    /// The options are invalid, which exits with code 2.
+    Usage(String),
+    /// The input can't be read or simplified, which exits with code 1.
+    Input(String),
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Csv,
+    Text,
+    GeoJson,
+}
+
+
+enum Algorithm {
+    Rdp { epsilon: f64 },
+    RdpCount { n_points: usize },
+    Visvalingam { min_area: f64 },
+    ImaiIri { epsilon: f64 },
+    Lang { epsilon: f64, look_ahead: usize },
+    ReumannWitkam { tolerance: f64 },
+    Opheim { tolerance: f64, max_distance: f64 },
+}
+
+
+impl Algorithm {
+    fn from_matches(matches: &ArgMatches<'_>) -> Result<Algorithm, Error> {
+        let name = matches.value_of("algorithm").unwrap();
+        if let Some(n_points) = parse_value(matches, "target-points")? {
+            return match name {
+                "rdp" => Ok(Algorithm::RdpCount { n_points }),
+                _ => Err(Error::Usage(format!("--target-points is only supported by rdp, not {}", name))),
+            };
+        }
+
+        let epsilon: f64 = parse_value(matches, "epsilon")?.unwrap();
+        if epsilon.is_nan() || epsilon < 0.0 {
+            return Err(Error::Usage(CurvedError::InvalidEpsilon(epsilon).to_string()));
+        }
+        let required = |option: &str| Error::Usage(format!("--{} is required by {}", option, name));
+        Ok(match name {
+            "rdp" => Algorithm::Rdp { epsilon },
+            "visvalingam" => Algorithm::Visvalingam { min_area: epsilon },
+            "imai-iri" => Algorithm::ImaiIri { epsilon },
+            "lang" => Algorithm::Lang {
+                epsilon,
+                look_ahead: parse_value(matches, "look-ahead")?.ok_or_else(|| required("look-ahead"))?,
+            },
+            "reumann-witkam" => Algorithm::ReumannWitkam { tolerance: epsilon },
+            "opheim" => {
+                let max_distance: f64 = parse_value(matches, "max-distance")?.ok_or_else(|| required("max-distance"))?;
+                if max_distance.is_nan() || max_distance < 0.0 {
+                    return Err(Error::Usage(CurvedError::InvalidMaxDistance(max_distance).to_string()));
+                }
+                Algorithm::Opheim { tolerance: epsilon, max_distance }
+            }
+            _ => unreachable!(),
+        })
+    }
+
+    fn simplify(&self, points: ArrayView2<'_, f64>) -> Result<Array1<bool>, Error> {
+        Ok(match *self {
+            Algorithm::Rdp { epsilon } => curved::try_rdp(points, epsilon).map_err(|e| Error::Input(e.to_string()))?,
+            Algorithm::RdpCount { n_points } => curved::rdp_count(points, n_points),
+            Algorithm::Visvalingam { min_area } => curved::visvalingam(points, min_area),
+            Algorithm::ImaiIri { epsilon } => curved::imai_iri(points, epsilon),
+            Algorithm::Lang { epsilon, look_ahead } => curved::lang(points, epsilon, look_ahead),
+            Algorithm::ReumannWitkam { tolerance } => curved::reumann_witkam(points, tolerance),
+            Algorithm::Opheim { tolerance, max_distance } => curved::opheim(points, tolerance, max_distance),
+        })
+    }
+}
+
+
+fn parse_value<T: std::str::FromStr>(matches: &ArgMatches<'_>, name: &str) -> Result<Option<T>, Error> {
+    matches.value_of(name)
+        .map(|value| value.parse().map_err(|_| Error::Usage(format!("invalid value for --{}: {}", name, value))))
+        .transpose()
+}
+
+
+/// Rows of coordinates read from CSV or whitespace separated text, as they were read.
+struct Table<'a> {
+    header: Option<&'a str>,
+    rows: Vec<&'a str>,
+    points: Array2<f64>,
+}
+
+
+/// Reads the first `dims` columns of each row as a point, or every column if `dims` is `None`.
+/// A first row that isn't numeric is taken as a header, and blank rows and rows starting with
+/// `#` are skipped.
+fn parse_table(text: &str, format: Format, dims: Option<usize>) -> Result<Table<'_>, Error> {
+    let mut table = Table { header: None, rows: Vec::new(), points: Array2::zeros((0, 0)) };
+    let mut coordinates = Vec::new();
+    let mut width = dims;
+    for (number, line) in text.lines().enumerate().map(|(i, line)| (i + 1, line)) {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = match format {
+            Format::Csv => line.split(',').map(str::trim).collect(),
+            _ => line.split_whitespace().collect(),
+        };
+        if table.rows.is_empty() && table.header.is_none() && fields[0].parse::<f64>().is_err() {
+            table.header = Some(line);
+            continue;
+        }
+
+        let width = *width.get_or_insert(fields.len());
+        if fields.len() < width || (dims.is_none() && fields.len() > width) {
+            return Err(Error::Input(format!("line {}: expected {} coordinates, found {}", number, width, fields.len())));
+        }
+        for field in &fields[..width] {
+            match field.parse::<f64>() {
+                Ok(value) if value.is_finite() => coordinates.push(value),
+                _ => return Err(Error::Input(format!("line {}: {:?} is not a finite number", number, field))),
+            }
+        }
+        table.rows.push(line);
+    }
+
+    table.points = Array2::from_shape_vec((table.rows.len(), width.unwrap_or(0)), coordinates).unwrap();
+    Ok(table)
+}
+
+
+/// The positions of the LineString in a GeoJSON geometry or feature.
+fn line_string(document: &mut Value) -> Result<&mut Vec<Value>, Error> {
+    let kind = document.get("type").and_then(Value::as_str).map(str::to_owned);
+    let positions = match kind.as_deref() {
+        Some("Feature") => return document.get_mut("geometry").ok_or_else(not_line_string).and_then(line_string),
+        Some("LineString") => document.get_mut("coordinates").and_then(Value::as_array_mut),
+        _ => None,
+    };
+    positions.ok_or_else(not_line_string)
+}
+
+
+fn not_line_string() -> Error {
+    Error::Input("GeoJSON input must be a LineString, or a Feature with a LineString geometry".to_string())
+}
+
+
+/// Reads the first `dims` coordinates of each position as a point, or every coordinate if `dims`
+/// is `None`.
+fn parse_positions(positions: &[Value], dims: Option<usize>) -> Result<Array2<f64>, Error> {
+    let mut coordinates = Vec::new();
+    let mut width = dims;
+    for (i, position) in positions.iter().enumerate() {
+        let position = position.as_array()
+            .ok_or_else(|| Error::Input(format!("position {} is not an array", i)))?;
+        let width = *width.get_or_insert(position.len());
+        if position.len() < width || (dims.is_none() && position.len() > width) {
+            return Err(Error::Input(format!("position {}: expected {} coordinates, found {}", i, width, position.len())));
+        }
+        for value in &position[..width] {
+            match value.as_f64() {
+                Some(value) => coordinates.push(value),
+                None => return Err(Error::Input(format!("position {}: {} is not a number", i, value))),
+            }
+        }
+    }
+
+    Ok(Array2::from_shape_vec((positions.len(), width.unwrap_or(0)), coordinates).unwrap())
+}
+
+
+/// Guesses the format from the input: GeoJSON is an object, and CSV has commas in its first row,
+/// skipping blank rows and comments as [`parse_table`] does.
+fn detect_format(text: &str) -> Format {
+    let first_row = text.lines().find(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'));
+    if text.trim_start().starts_with('{') {
+        Format::GeoJson
+    } else if first_row.is_some_and(|line| line.contains(',')) {
+        Format::Csv
+    } else {
+        Format::Text
+    }
+}
+
+
+fn read_input(path: Option<&str>) -> Result<String, Error> {
+    match path {
+        None | Some("-") => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text)
+                .map_err(|e| Error::Input(format!("couldn't read standard input: {}", e)))?;
+            Ok(text)
+        }
+        Some(path) => fs::read_to_string(path).map_err(|e| Error::Input(format!("couldn't read {}: {}", path, e))),
+    }
+}
+
+
+fn check_points(points: ArrayView2<'_, f64>) -> Result<(), Error> {
+    if points.nrows() == 0 {
+        return Err(Error::Input(CurvedError::EmptyInput.to_string()));
+    }
+    if let Some(i) = points.outer_iter().position(|point| !point.iter().all(|v| v.is_finite())) {
+        return Err(Error::Input(CurvedError::NonFiniteCoordinate(i).to_string()));
+    }
+    Ok(())
+}
+
+
+fn write_indices(out: &mut impl Write, mask: &Array1<bool>) -> io::Result<()> {
+    for (i, _) in mask.iter().enumerate().filter(|(_, &keep)| keep) {
+        writeln!(out, "{}", i)?;
+    }
+    Ok(())
+}
+
+
+fn run(matches: &ArgMatches<'_>) -> Result<(), Error> {
+    let algorithm = Algorithm::from_matches(matches)?;
+    let dims = parse_value(matches, "dims")?;
+    if dims == Some(0) {
+        return Err(Error::Usage("--dims must be at least 1".to_string()));
+    }
+    let indices = matches.value_of("output") == Some("indices");
+
+    let text = read_input(matches.value_of("input"))?;
+    let format = match matches.value_of("format") {
+        Some("csv") => Format::Csv,
+        Some("text") => Format::Text,
+        Some("geojson") => Format::GeoJson,
+        _ => detect_format(&text),
+    };
+
+    let stdout = io::stdout();
+    let mut out = BufWriter::new(stdout.lock());
+    let written = if format == Format::GeoJson {
+        let mut document: Value = serde_json::from_str(&text)
+            .map_err(|e| Error::Input(format!("invalid GeoJSON: {}", e)))?;
+        let positions = line_string(&mut document)?;
+        let points = parse_positions(positions, dims)?;
+        check_points(points.view())?;
+        let mask = algorithm.simplify(points.view())?;
+        if indices {
+            write_indices(&mut out, &mask)
+        } else {
+            let retained = positions.iter().zip(mask.iter()).filter(|(_, &keep)| keep).map(|(p, _)| p.clone());
+            *positions = retained.collect();
+            serde_json::to_writer(&mut out, &document).map_err(io::Error::from).and_then(|_| writeln!(out))
+        }
+    } else {
+        let table = parse_table(&text, format, dims)?;
+        check_points(table.points.view())?;
+        let mask = algorithm.simplify(table.points.view())?;
+        if indices {
+            write_indices(&mut out, &mask)
+        } else {
+            table.header.into_iter()
+                .chain(table.rows.iter().zip(mask.iter()).filter(|(_, &keep)| keep).map(|(row, _)| *row))
+                .try_for_each(|row| writeln!(out, "{}", row))
+        }
+    };
+
+    // A closed pipe just means the reader has seen enough.
+    match written.and_then(|_| out.flush()) {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(Error::Input(format!("couldn't write output: {}", e))),
+        _ => Ok(()),
+    }
+}
+
+
+fn app() -> App<'static, 'static> {
+    App::new("curved")
+        .version(crate_version!())
+        .about("Simplifies a curve read from a file or standard input.")
+        .arg(Arg::with_name("input")
+            .help("The file to read, or - for standard input, which is the default"))
+        .arg(Arg::with_name("algorithm")
+            .long("algorithm")
+            .short("a")
+            .takes_value(true)
+            .possible_values(&["rdp", "visvalingam", "imai-iri", "lang", "reumann-witkam", "opheim"])
+            .default_value("rdp")
+            .help("The simplification algorithm"))
+        .arg(Arg::with_name("epsilon")
+            .long("epsilon")
+            .short("e")
+            .takes_value(true)
+            .allow_hyphen_values(true)
+            .required_unless("target-points")
+            .conflicts_with("target-points")
+            .help("The tolerance, or the minimum area for visvalingam"))
+        .arg(Arg::with_name("target-points")
+            .long("target-points")
+            .short("n")
+            .takes_value(true)
+            .help("The number of points to retain, instead of a tolerance (rdp only)"))
+        .arg(Arg::with_name("dims")
+            .long("dims")
+            .short("d")
+            .takes_value(true)
+            .help("The number of coordinates of each point to simplify, defaulting to all of them"))
+        .arg(Arg::with_name("look-ahead")
+            .long("look-ahead")
+            .takes_value(true)
+            .help("The number of points to look ahead (lang only)"))
+        .arg(Arg::with_name("max-distance")
+            .long("max-distance")
+            .takes_value(true)
+            .allow_hyphen_values(true)
+            .help("The maximum distance between retained points (opheim only)"))
+        .arg(Arg::with_name("format")
+            .long("format")
+            .short("f")
+            .takes_value(true)
+            .possible_values(&["csv", "text", "geojson"])
+            .help("The input format, guessed from the input by default"))
+        .arg(Arg::with_name("output")
+            .long("output")
+            .short("o")
+            .takes_value(true)
+            .possible_values(&["points", "indices"])
+            .default_value("points")
+            .help("Whether to write the retained points, or their indices"))
+}
+
+
+fn main() {
+    let matches = match app().get_matches_safe() {
+        Ok(matches) => matches,
+        Err(error) if error.use_stderr() => {
+            eprintln!("{}", error.message);
+            process::exit(2);
+        }
+        Err(error) => error.exit(),
+    };
+
+    if let Err(error) = run(&matches) {
+        let (message, code) = match error {
+            Error::Usage(message) => (message, 2),
+            Error::Input(message) => (message, 1),
+        };
+        eprintln!("error: {}", message);
+        process::exit(code);
+    }
+}
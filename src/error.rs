@@ -0,0 +1,49 @@
+use std::fmt;
+
+
+/// The ways in which simplification inputs can be invalid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CurvedError {
+    /// The curve has no points.
+    EmptyInput,
+    /// The points of the curve have no coordinates.
+    ZeroDimensional,
+    /// The points of the curve have fewer than the given number of coordinates.
+    TooFewDimensions(usize),
+    /// The point does not have the given number of coordinates, like the points before it.
+    DimensionMismatch(usize),
+    /// The point at the given index has an infinite or NaN coordinate.
+    NonFiniteCoordinate(usize),
+    /// The tolerance is negative or NaN.
+    InvalidEpsilon(f64),
+    /// The tolerance with the given name, such as `min_area`, is negative or NaN.
+    InvalidTolerance(&'static str, f64),
+    /// The maximum distance between retained points is negative or NaN.
+    InvalidMaxDistance(f64),
+    /// The weights do not match the number of coordinates, are negative or non-finite, or are
+    /// used with geographic distances.
+    InvalidWeights,
+    /// The offsets of the parts of a batch are not increasing from zero to the number of points.
+    InvalidOffsets,
+}
+
+
+impl fmt::Display for CurvedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CurvedError::EmptyInput => write!(f, "points must not be empty"),
+            CurvedError::ZeroDimensional => write!(f, "points must have at least one dimension"),
+            CurvedError::TooFewDimensions(n) => write!(f, "points must have at least {} dimensions", n),
+            CurvedError::DimensionMismatch(n) => write!(f, "points must all have {} dimensions", n),
+            CurvedError::NonFiniteCoordinate(i) => write!(f, "point {} has a non-finite coordinate", i),
+            CurvedError::InvalidEpsilon(epsilon) => write!(f, "epsilon must be non-negative, not {}", epsilon),
+            CurvedError::InvalidTolerance(name, value) => write!(f, "{} must be non-negative, not {}", name, value),
+            CurvedError::InvalidMaxDistance(distance) => write!(f, "max_distance must be non-negative, not {}", distance),
+            CurvedError::InvalidWeights => write!(f, "weights must be one non-negative value per dimension, and not geographic"),
+            CurvedError::InvalidOffsets => write!(f, "offsets must increase from zero to the number of points"),
+        }
+    }
+}
+
+
+impl std::error::Error for CurvedError {}
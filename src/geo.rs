@@ -0,0 +1,69 @@
+use geo_types::{Coord, CoordFloat, LineString, MultiLineString, MultiPolygon, Polygon};
+use ndarray::{Array1, Array2};
+
+use crate::{rdp_with_options, CurveFloat, RdpOptions};
+
+
+/// Simplification of `geo-types` geometries using the Ramer-Douglas-Peucker algorithm.
+///
+/// Returns the simplified geometry along with the mask of the coordinates retained. The rings of
+/// polygons are simplified as closed, whatever the `closed` option, and keep at least four
+/// coordinates if they have at least three distinct coordinates.
+pub trait CurvedSimplify<T>: Sized {
+    /// The mask of the coordinates retained, in the same structure as the geometry.
+    type Mask;
+
+    fn curved_simplify(&self, epsilon: T, options: &RdpOptions) -> (Self, Self::Mask);
+}
+
+
+impl<T: CoordFloat + CurveFloat> CurvedSimplify<T> for LineString<T> {
+    type Mask = Array1<bool>;
+
+    fn curved_simplify(&self, epsilon: T, options: &RdpOptions) -> (Self, Self::Mask) {
+        if self.0.is_empty() {
+            return (self.clone(), Array1::from_elem((0,), false));
+        }
+
+        let points = Array2::from_shape_fn((self.0.len(), 2), |(i, j)| if j == 0 { self.0[i].x } else { self.0[i].y });
+        let mask = rdp_with_options(points.view(), epsilon, options);
+        let coords: Vec<Coord<T>> = self.0.iter().zip(mask.iter()).filter(|(_, &keep)| keep).map(|(&coord, _)| coord).collect();
+        (LineString::new(coords), mask)
+    }
+}
+
+
+impl<T: CoordFloat + CurveFloat> CurvedSimplify<T> for Polygon<T> {
+    /// The masks of the exterior ring followed by the interior rings.
+    type Mask = Vec<Array1<bool>>;
+
+    fn curved_simplify(&self, epsilon: T, options: &RdpOptions) -> (Self, Self::Mask) {
+        let options = RdpOptions { closed: true, ..options.clone() };
+        let (exterior, exterior_mask) = self.exterior().curved_simplify(epsilon, &options);
+        let (interiors, mut masks): (Vec<_>, Vec<_>) = self.interiors().iter()
+            .map(|ring| ring.curved_simplify(epsilon, &options))
+            .unzip();
+        masks.insert(0, exterior_mask);
+        (Polygon::new(exterior, interiors), masks)
+    }
+}
+
+
+impl<T: CoordFloat + CurveFloat> CurvedSimplify<T> for MultiLineString<T> {
+    type Mask = Vec<Array1<bool>>;
+
+    fn curved_simplify(&self, epsilon: T, options: &RdpOptions) -> (Self, Self::Mask) {
+        let (lines, masks) = self.0.iter().map(|line| line.curved_simplify(epsilon, options)).unzip();
+        (MultiLineString::new(lines), masks)
+    }
+}
+
+
+impl<T: CoordFloat + CurveFloat> CurvedSimplify<T> for MultiPolygon<T> {
+    type Mask = Vec<Vec<Array1<bool>>>;
+
+    fn curved_simplify(&self, epsilon: T, options: &RdpOptions) -> (Self, Self::Mask) {
+        let (polygons, masks) = self.0.iter().map(|polygon| polygon.curved_simplify(epsilon, options)).unzip();
+        (MultiPolygon::new(polygons), masks)
+    }
+}
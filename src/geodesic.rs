@@ -0,0 +1,95 @@
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2, Axis};
+
+use crate::{CurveFloat, LineStartPointBuffer};
+
+
+/// The mean radius of the Earth in metres.
+const MEAN_RADIUS: f64 = 6_371_008.8;
+
+/// The semi-major axis of the WGS84 ellipsoid in metres.
+const WGS84_A: f64 = 6_378_137.0;
+
+/// The flattening of the WGS84 ellipsoid.
+const WGS84_F: f64 = 1.0 / 298.257_223_563;
+
+
+/// The model of the Earth that geographic distances are measured on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ellipsoid {
+    /// A sphere with the mean radius of the Earth.
+    Sphere,
+    /// The WGS84 ellipsoid, approximated by a sphere with the local radius of curvature at each
+    /// point.
+    Wgs84,
+}
+
+
+impl Ellipsoid {
+    fn radius(self, sin_latitude: f64) -> f64 {
+        match self {
+            Ellipsoid::Sphere => MEAN_RADIUS,
+            Ellipsoid::Wgs84 => {
+                // The geometric mean of the meridional and prime vertical radii of curvature.
+                let e_2 = WGS84_F * (2.0 - WGS84_F);
+                let w_2 = 1.0 - e_2 * sin_latitude.powi(2);
+                WGS84_A * (1.0 - e_2).sqrt() / w_2
+            },
+        }
+    }
+}
+
+
+/// Converts longitudes and latitudes in degrees, in the first two columns of `points`, to
+/// points on the unit sphere.
+pub(crate) fn unit_vectors<F: CurveFloat>(points: ArrayView2<'_, F>) -> Array2<F> {
+    let mut unit = Array2::zeros((points.len_of(Axis(0)), 3));
+    for (point, mut vector) in points.outer_iter().zip(unit.outer_iter_mut()) {
+        let (sin_lon, cos_lon) = point[0].to_radians().sin_cos();
+        let (sin_lat, cos_lat) = point[1].to_radians().sin_cos();
+        vector[0] = cos_lat * cos_lon;
+        vector[1] = cos_lat * sin_lon;
+        vector[2] = sin_lat;
+    }
+    unit
+}
+
+
+/// Calculates the squared distances in metres from the great circle through the start (A) and
+/// end (B) to each point, all given as points on the unit sphere.
+pub(crate) fn cross_track_distances_2<F: CurveFloat>(
+    start: ArrayView1<'_, F>,
+    end: ArrayView1<'_, F>,
+    buffer: &LineStartPointBuffer<'_, F>,
+    ellipsoid: Ellipsoid
+) -> Array1<F> {
+    // The unit normal to the great circle through the start and end.
+    let normal = Array1::from(vec![
+        start[1] * end[2] - start[2] * end[1],
+        start[2] * end[0] - start[0] * end[2],
+        start[0] * end[1] - start[1] * end[0],
+    ]);
+    let normal_magnitude = normal.dot(&normal).sqrt();
+
+    // The angle subtended at the centre by each point and the great circle. If the start and
+    // end coincide there is no great circle, so use the angle to the start instead, from the
+    // length of the chord between them.
+    let mut angles = if normal_magnitude.is_zero() {
+        buffer.magnitudes_2.mapv(|ac_2| {
+            let half = F::from(0.5).unwrap();
+            (half * ac_2.sqrt()).min(F::one()).asin() * (F::one() + F::one())
+        })
+    } else {
+        // The point (C) to start (A) vector projected onto the normal is the sine of the angle,
+        // as the start is perpendicular to the normal.
+        let normal_unit = normal / normal_magnitude;
+        buffer.vectors.dot(&normal_unit).mapv(|sin| sin.abs().min(F::one()).asin())
+    };
+
+    // Scale the angles by the radius at each point, using the sine of its latitude.
+    for (angle, vector) in angles.iter_mut().zip(buffer.vectors.outer_iter()) {
+        let sin_latitude = (vector[2] + start[2]).to_f64().unwrap();
+        let radius = F::from(ellipsoid.radius(sin_latitude)).unwrap();
+        *angle = (*angle * radius).powi(2);
+    }
+    angles
+}
@@ -0,0 +1,159 @@
+use std::fmt;
+
+use geojson::{Feature, FeatureCollection, Geometry, Position, Value};
+use ndarray::{Array1, Array2, ArrayView2};
+
+use crate::strip::check_max_distance;
+use crate::{
+    check_options, imai_iri, lang, opheim, rdp_with_options, reumann_witkam, visvalingam, CurvedError,
+    RdpOptions,
+};
+
+
+/// The algorithm used to simplify each curve of a GeoJSON geometry.
+#[derive(Debug, Clone)]
+pub enum Algorithm {
+    /// The Ramer-Douglas-Peucker algorithm with the given options. Rings are always simplified as
+    /// closed.
+    Rdp(RdpOptions),
+    /// The Visvalingam-Whyatt algorithm, with the tolerance as the minimum area.
+    Visvalingam,
+    /// The Imai-Iri algorithm.
+    ImaiIri,
+    /// The Lang algorithm, looking ahead the given number of points.
+    Lang { look_ahead: usize },
+    /// The Reumann-Witkam algorithm.
+    ReumannWitkam,
+    /// The Opheim algorithm, with the given maximum distance between retained points.
+    Opheim { max_distance: f64 },
+}
+
+
+impl Default for Algorithm {
+    fn default() -> Algorithm {
+        Algorithm::Rdp(RdpOptions::default())
+    }
+}
+
+
+/// The ways in which GeoJSON simplification can fail.
+#[derive(Debug)]
+pub enum GeoJsonError {
+    /// The input isn't a valid GeoJSON FeatureCollection.
+    Parse(Box<geojson::Error>),
+    /// The tolerance or the options are invalid.
+    Simplify(CurvedError),
+}
+
+
+impl fmt::Display for GeoJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GeoJsonError::Parse(error) => write!(f, "invalid GeoJSON: {}", error),
+            GeoJsonError::Simplify(error) => error.fmt(f),
+        }
+    }
+}
+
+
+impl std::error::Error for GeoJsonError {}
+
+
+impl From<geojson::Error> for GeoJsonError {
+    fn from(error: geojson::Error) -> GeoJsonError {
+        GeoJsonError::Parse(Box::new(error))
+    }
+}
+
+
+impl From<CurvedError> for GeoJsonError {
+    fn from(error: CurvedError) -> GeoJsonError {
+        GeoJsonError::Simplify(error)
+    }
+}
+
+
+/// Simplifies every curve of every feature, returning a new collection with the same properties,
+/// ids and other members.
+///
+/// LineStrings, Polygons, their Multi* forms and GeometryCollections are simplified in the plane
+/// of the first two coordinates of each position, and any further coordinates are kept with the
+/// positions retained. Points are left alone. The rings of polygons keep their closing position,
+/// and are left alone if they would otherwise be reduced to fewer than four positions.
+pub fn simplify_feature_collection(collection: &FeatureCollection, epsilon: f64, algorithm: &Algorithm) -> Result<FeatureCollection, CurvedError> {
+    let options = match algorithm {
+        Algorithm::Rdp(options) => options.clone(),
+        _ => RdpOptions::default(),
+    };
+    check_options(Array2::<f64>::zeros((0, 2)).view(), epsilon, &options)?;
+    if let Algorithm::Opheim { max_distance } = algorithm {
+        check_max_distance(*max_distance)?;
+    }
+
+    let features = collection.features.iter()
+        .map(|feature| Feature {
+            geometry: feature.geometry.as_ref().map(|geometry| simplify_geometry(geometry, epsilon, algorithm)),
+            ..feature.clone()
+        })
+        .collect();
+    Ok(FeatureCollection { features, ..collection.clone() })
+}
+
+
+/// Parses a GeoJSON FeatureCollection, simplifies it and serialises the result. See
+/// [`simplify_feature_collection`].
+pub fn simplify_geojson(geojson: &str, epsilon: f64, algorithm: &Algorithm) -> Result<String, GeoJsonError> {
+    let collection: FeatureCollection = geojson.parse()?;
+    Ok(simplify_feature_collection(&collection, epsilon, algorithm)?.to_string())
+}
+
+
+fn simplify_geometry(geometry: &Geometry, epsilon: f64, algorithm: &Algorithm) -> Geometry {
+    let curve = |positions: &Vec<Position>| simplify_curve(positions, epsilon, algorithm, false);
+    let ring = |positions: &Vec<Position>| simplify_curve(positions, epsilon, algorithm, true);
+    let value = match &geometry.value {
+        Value::LineString(line) => Value::LineString(curve(line)),
+        Value::MultiLineString(lines) => Value::MultiLineString(lines.iter().map(curve).collect()),
+        Value::Polygon(rings) => Value::Polygon(rings.iter().map(ring).collect()),
+        Value::MultiPolygon(polygons) => Value::MultiPolygon(
+            polygons.iter().map(|rings| rings.iter().map(ring).collect()).collect()),
+        Value::GeometryCollection(geometries) => Value::GeometryCollection(
+            geometries.iter().map(|geometry| simplify_geometry(geometry, epsilon, algorithm)).collect()),
+        value => value.clone(),
+    };
+    Geometry { value, ..geometry.clone() }
+}
+
+
+fn simplify_curve(positions: &[Position], epsilon: f64, algorithm: &Algorithm, ring: bool) -> Vec<Position> {
+    if positions.len() < 3 || positions.iter().any(|position| position.len() < 2) {
+        return positions.to_vec();
+    }
+
+    let points = Array2::from_shape_fn((positions.len(), 2), |(i, j)| positions[i][j]);
+    let mask = simplify_points(points.view(), epsilon, algorithm, ring);
+    let simplified: Vec<Position> = positions.iter().zip(mask.iter())
+        .filter(|(_, &keep)| keep)
+        .map(|(position, _)| position.clone())
+        .collect();
+    if ring && simplified.len() < 4 {
+        positions.to_vec()
+    } else {
+        simplified
+    }
+}
+
+
+fn simplify_points(points: ArrayView2<'_, f64>, epsilon: f64, algorithm: &Algorithm, ring: bool) -> Array1<bool> {
+    match algorithm {
+        Algorithm::Rdp(options) => {
+            let options = RdpOptions { closed: ring || options.closed, ..options.clone() };
+            rdp_with_options(points, epsilon, &options)
+        }
+        Algorithm::Visvalingam => visvalingam(points, epsilon),
+        Algorithm::ImaiIri => imai_iri(points, epsilon),
+        Algorithm::Lang { look_ahead } => lang(points, epsilon, *look_ahead),
+        Algorithm::ReumannWitkam => reumann_witkam(points, epsilon),
+        Algorithm::Opheim { max_distance } => opheim(points, epsilon, *max_distance),
+    }
+}
@@ -0,0 +1,85 @@
+use ndarray::{concatenate, s, Axis, Array1, Array2, ArrayView2};
+
+use crate::{rdp_with_options, CurveFloat, RdpOptions};
+
+
+/// A polygon, as an exterior ring and any number of interior rings (holes). Each ring is an
+/// array of points, which may or may not repeat the first point at the end.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon<F> {
+    pub exterior: Array2<F>,
+    pub interiors: Vec<Array2<F>>,
+}
+
+
+/// A collection of polygons, such as a country and its islands.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiPolygon<F> {
+    pub polygons: Vec<Polygon<F>>,
+}
+
+
+/// A collection of open curves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiLineString<F> {
+    pub lines: Vec<Array2<F>>,
+}
+
+
+/// Simplifies a ring using the Ramer-Douglas-Peucker algorithm, treating it as closed whatever
+/// the `closed` option.
+///
+/// The simplified ring always repeats its first point at the end, and has at least four points
+/// if the ring has at least three distinct points.
+pub fn rdp_ring<F: CurveFloat>(ring: ArrayView2<'_, F>, epsilon: F, options: &RdpOptions) -> Array2<F> {
+    let len = ring.len_of(Axis(0));
+    if len == 0 {
+        return ring.to_owned();
+    }
+
+    let options = RdpOptions { closed: true, ..options.clone() };
+    let simplified = retained(ring, &rdp_with_options(ring, epsilon, &options));
+    if ring.row(0) != ring.row(len - 1) {
+        concatenate![Axis(0), simplified, ring.slice(s![..1, ..])]
+    } else {
+        simplified
+    }
+}
+
+
+/// Simplifies every ring of a polygon using the Ramer-Douglas-Peucker algorithm. See
+/// [`rdp_ring`].
+pub fn rdp_polygon<F: CurveFloat>(polygon: &Polygon<F>, epsilon: F, options: &RdpOptions) -> Polygon<F> {
+    Polygon {
+        exterior: rdp_ring(polygon.exterior.view(), epsilon, options),
+        interiors: polygon.interiors.iter().map(|ring| rdp_ring(ring.view(), epsilon, options)).collect(),
+    }
+}
+
+
+/// Simplifies every ring of every polygon using the Ramer-Douglas-Peucker algorithm. See
+/// [`rdp_ring`].
+pub fn rdp_multi_polygon<F: CurveFloat>(multi_polygon: &MultiPolygon<F>, epsilon: F, options: &RdpOptions) -> MultiPolygon<F> {
+    MultiPolygon {
+        polygons: multi_polygon.polygons.iter().map(|polygon| rdp_polygon(polygon, epsilon, options)).collect(),
+    }
+}
+
+
+/// Simplifies every curve using the Ramer-Douglas-Peucker algorithm with the given options.
+pub fn rdp_multi_line_string<F: CurveFloat>(multi_line_string: &MultiLineString<F>, epsilon: F, options: &RdpOptions) -> MultiLineString<F> {
+    MultiLineString {
+        lines: multi_line_string.lines.iter()
+            .map(|line| match line.len_of(Axis(0)) {
+                0 => line.clone(),
+                _ => retained(line.view(), &rdp_with_options(line.view(), epsilon, options)),
+            })
+            .collect(),
+    }
+}
+
+
+pub(crate) fn retained<F: CurveFloat>(points: ArrayView2<'_, F>, mask: &Array1<bool>) -> Array2<F> {
+    let indices: Vec<usize> = mask.iter().enumerate().filter(|(_, &keep)| keep).map(|(i, _)| i).collect();
+    points.select(Axis(0), &indices)
+}
@@ -0,0 +1,171 @@
+use std::f64::consts::PI;
+
+use ndarray::{s, Axis, Array1, ArrayView2};
+
+use crate::{line_point_distances_2, max_distance, CurveFloat, DistanceMetric, LineStartPointBuffer};
+
+
+/// Simplifies a curve to the fewest points within a tolerance, using the Imai-Iri approach.
+///
+/// A shortcut between two points is allowed if every point in between is within `epsilon` of the
+/// line through them, exactly as [`rdp`] decides whether to stop splitting. The shortest path of
+/// shortcuts from the first to the last point is retained, so never more points than [`rdp`].
+///
+/// The shortcut graph is explored without being stored, so memory is O(n). For 2D points, the
+/// directions from each point that would keep every point so far within `epsilon` are tracked,
+/// so that most shortcuts are decided in constant time and the search from a point stops once no
+/// direction is left, which keeps curves of around 10⁴ points practical. Other dimensions check
+/// every shortcut directly, which takes O(n³) time. The endpoints are always retained.
+///
+/// [`rdp`]: crate::rdp
+pub fn imai_iri<F: CurveFloat>(points: ArrayView2<'_, F>, epsilon: F) -> Array1<bool> {
+    let len = points.len_of(Axis(0));
+    let mut mask = Array1::from_elem((len,), false);
+    if len == 0 {
+        return mask;
+    }
+
+    // As shortcuts only go forwards, the fewest shortcuts to each point is known once every point
+    // before it has been visited.
+    let mut hops = vec![usize::MAX; len];
+    let mut previous = vec![0; len];
+    hops[0] = 0;
+    for start in 0..len - 1 {
+        let mut shortcut = |end: usize| {
+            if hops[start] + 1 < hops[end] {
+                hops[end] = hops[start] + 1;
+                previous[end] = start;
+            }
+        };
+        if points.len_of(Axis(1)) == 2 {
+            shortcuts_2d(points, epsilon, start, &mut shortcut);
+        } else {
+            for end in start + 1..len {
+                if within(points, epsilon, start, end) {
+                    shortcut(end);
+                }
+            }
+        }
+    }
+
+    let mut i = len - 1;
+    mask[i] = true;
+    while i > 0 {
+        i = previous[i];
+        mask[i] = true;
+    }
+
+    mask
+}
+
+
+/// Whether every point between `start` and `end` is within epsilon of the line through them,
+/// calculated in the same way as [`rdp`](crate::rdp).
+fn within<F: CurveFloat>(points: ArrayView2<'_, F>, epsilon: F, start: usize, end: usize) -> bool {
+    if end == start + 1 {
+        return true;
+    }
+    let buffer = LineStartPointBuffer::from_points(points.slice(s![start..=end, ..]), None);
+    let distances_2 = line_point_distances_2(points.row(start), points.row(end), &buffer, DistanceMetric::Line);
+    max_distance(&distances_2).1 <= epsilon.powi(2)
+}
+
+
+fn shortcuts_2d<F: CurveFloat>(points: ArrayView2<'_, F>, epsilon: F, start: usize, shortcut: &mut impl FnMut(usize)) {
+    // Directions are angles modulo π, as lines are undirected. The inner directions certainly
+    // keep every point within epsilon and the outer directions might, with a margin for the
+    // rounding of both the angles and the direct calculation. Only the directions in between are
+    // checked directly.
+    let mut inner = Directions::all();
+    let mut outer = Directions::all();
+    let tolerance = epsilon.to_f64().unwrap();
+    let margin = 64.0 * F::epsilon().to_f64().unwrap();
+    let (x0, y0) = (points[[start, 0]].to_f64().unwrap(), points[[start, 1]].to_f64().unwrap());
+    let scale = x0.abs().max(y0.abs());
+    for end in start + 1..points.len_of(Axis(0)) {
+        let (x, y) = (points[[end, 0]].to_f64().unwrap() - x0, points[[end, 1]].to_f64().unwrap() - y0);
+        let r = x.hypot(y);
+        let angle = y.atan2(x).rem_euclid(PI);
+        let error = margin * (scale + r);
+
+        let allowed = if r > 0.0 && inner.contains(angle, -error / r) {
+            true
+        } else {
+            (r == 0.0 || outer.contains(angle, error / r)) && within(points, epsilon, start, end)
+        };
+        if allowed {
+            shortcut(end);
+        }
+
+        // Restrict the directions to those keeping this point within epsilon.
+        inner.restrict(angle, half_width(tolerance - 2.0 * error, r));
+        outer.restrict(angle, half_width(tolerance + 2.0 * error, r));
+        if outer.is_empty() {
+            break;
+        }
+    }
+}
+
+
+/// The angle either side of the direction to a point at distance `r` within which lines pass
+/// within `epsilon` of it, or `None` for every direction. Negative if there are none.
+fn half_width(epsilon: f64, r: f64) -> Option<f64> {
+    if epsilon >= r {
+        None
+    } else {
+        Some((epsilon / r).max(-1.0).asin())
+    }
+}
+
+
+/// A set of directions, as disjoint intervals of angles in [0, π).
+struct Directions {
+    intervals: Vec<(f64, f64)>,
+}
+
+
+impl Directions {
+    fn all() -> Directions {
+        Directions { intervals: vec![(0.0, PI)] }
+    }
+
+    /// Whether the angle is within the directions, grown by the given tolerance (or shrunk if
+    /// it is negative).
+    fn contains(&self, angle: f64, tolerance: f64) -> bool {
+        self.intervals.iter().any(|&(lo, hi)| {
+            [angle - PI, angle, angle + PI].iter().any(|&a| lo - tolerance <= a && a <= hi + tolerance)
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    fn restrict(&mut self, angle: f64, half_width: Option<f64>) {
+        let half_width = match half_width {
+            Some(half_width) => half_width,
+            None => return,
+        };
+
+        // The arc around the angle, wrapped into [0, π).
+        let (lo, hi) = (angle - half_width, angle + half_width);
+        let arc = if lo < 0.0 {
+            [(0.0, hi), (lo + PI, PI)]
+        } else if hi > PI {
+            [(0.0, hi - PI), (lo, PI)]
+        } else {
+            [(lo, hi), (f64::INFINITY, f64::NEG_INFINITY)]
+        };
+
+        let mut intervals = Vec::with_capacity(self.intervals.len() + 1);
+        for &(a, b) in self.intervals.iter() {
+            for &(c, d) in arc.iter() {
+                let (lo, hi) = (a.max(c), b.min(d));
+                if lo <= hi {
+                    intervals.push((lo, hi));
+                }
+            }
+        }
+        self.intervals = intervals;
+    }
+}
@@ -0,0 +1,41 @@
+use ndarray::{s, Axis, Array1, ArrayView2, Slice};
+
+use crate::{line_point_distances_2, max_distance, CurveFloat, DistanceMetric, LineStartPointBuffer};
+
+
+/// Simplifies a curve using the Lang algorithm.
+///
+/// From each retained point, the line to the point `look_ahead` points further on is tried. While
+/// any point in between is further than `epsilon` from the line, the end is moved back one
+/// point. The end is then retained and the search continues from it. Each point retained takes at
+/// most O(`look_ahead`²) time, regardless of the length of the curve. The endpoints are always
+/// retained. Works for N dimensional points.
+pub fn lang<F: CurveFloat>(points: ArrayView2<'_, F>, epsilon: F, look_ahead: usize) -> Array1<bool> {
+    let len = points.len_of(Axis(0));
+    let mut mask = Array1::from_elem((len,), false);
+    if len == 0 {
+        return mask;
+    }
+    mask[0] = true;
+
+    let epsilon_2 = epsilon.powi(2);
+    let look_ahead = look_ahead.max(1);
+    let mut key = 0;
+    while key < len - 1 {
+        // The vectors from the key are shared by every end tried.
+        let mut end = (key + look_ahead).min(len - 1);
+        let buffer = LineStartPointBuffer::from_points(points.slice(s![key..=end, ..]), None);
+        while end > key + 1 {
+            let distances_2 = line_point_distances_2(
+                points.row(key), points.row(end), &buffer.subset(Slice::from(..=end - key)), DistanceMetric::Line);
+            if max_distance(&distances_2).1 <= epsilon_2 {
+                break;
+            }
+            end -= 1;
+        }
+        mask[end] = true;
+        key = end;
+    }
+
+    mask
+}
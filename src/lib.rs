@@ -1,73 +1,517 @@
-use ndarray::{s, Axis, Array1, ArrayViewMut1, ArrayView1, ArrayView2, CowArray, Ix1, Ix2, Slice};
-use numpy::{convert::IntoPyArray, PyArray1, PyArray2};
-use pyo3::prelude::{pymodule, pyfunction, wrap_pyfunction, Py, PyModule, PyResult, Python};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
 
+use ndarray::{s, concatenate, Axis, Array1, ArrayView1, ArrayView2, CowArray, Ix1, Ix2, LinalgScalar, ScalarOperand, Slice};
+use num_traits::Float;
+
+mod batch;
+mod error;
+#[cfg(feature = "geo")]
+mod geo;
+mod geodesic;
+#[cfg(feature = "geojson")]
+mod geojson;
+mod geometry;
+mod imai_iri;
+mod lang;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "python")]
+mod python;
+mod shared;
+mod streaming;
+mod strip;
+mod topology;
+mod trajectory;
+mod visvalingam;
+
+pub use batch::{rdp_batch, try_rdp_batch};
+pub use error::CurvedError;
+#[cfg(feature = "geo")]
+pub use geo::CurvedSimplify;
+pub use geodesic::Ellipsoid;
+#[cfg(feature = "geojson")]
+pub use self::geojson::{simplify_feature_collection, simplify_geojson, Algorithm, GeoJsonError};
+pub use geometry::{rdp_multi_line_string, rdp_multi_polygon, rdp_polygon, rdp_ring, MultiLineString, MultiPolygon, Polygon};
+pub use imai_iri::imai_iri;
+pub use lang::lang;
+#[cfg(feature = "rayon")]
+pub use parallel::par_rdp;
+pub use shared::{rdp_shared, try_rdp_shared};
+pub use streaming::{StreamingSimplifier, DEFAULT_MAX_WINDOW};
+pub use strip::{opheim, reumann_witkam, try_opheim};
+pub use visvalingam::visvalingam;
+
+
+/// The floating point types that curves can be simplified in, namely `f32` and `f64`.
+pub trait CurveFloat: Float + ScalarOperand + LinalgScalar + Send + Sync {}
+
+
+impl<T> CurveFloat for T where T: Float + ScalarOperand + LinalgScalar + Send + Sync {}
+
+
+/// Simplifies a curve using the Ramer-Douglas-Peucker algorithm.
+///
+/// Returns a mask of the points to retain. Panics if there are no points; see [`try_rdp`] for
+/// a version that validates its inputs.
+pub fn rdp<F: CurveFloat>(points: ArrayView2<'_, F>, epsilon: F) -> Array1<bool> {
+    rdp_with_options(points, epsilon, &RdpOptions::default())
+}
+
+
+/// How the distance between a point and the line joining the endpoints of a range is measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DistanceMetric {
+    /// The perpendicular distance to the infinite line through the endpoints.
+    #[default]
+    Line,
+    /// The distance to the nearest point on the segment between the endpoints, so that points
+    /// beyond either end are measured to that endpoint.
+    Segment,
+    /// The distance in metres over the surface of the Earth to the great circle through the
+    /// endpoints. The first two coordinates are the longitude and latitude in degrees, and any
+    /// others are ignored.
+    Geographic(Ellipsoid),
+    /// The synchronized Euclidean distance for trajectories, from each point to the position
+    /// interpolated between the endpoints at the same time. The coordinate at index `time` is
+    /// the time, and the others are the position.
+    Synchronized {
+        time: usize,
+    },
+}
+
+
+/// Options for [`rdp_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct RdpOptions {
+    /// How the distance from each point to the simplified curve is measured.
+    pub metric: DistanceMetric,
+
+    /// Treat the points as a closed ring, whether or not the last point repeats the first.
+    ///
+    /// The ring is split at the point furthest from its start and each half is simplified, so
+    /// that at least three distinct points are always retained.
+    pub closed: bool,
+
+    /// Scale each coordinate by the corresponding weight when measuring distances, so that
+    /// coordinates in different units can be compared. Not supported for geographic distances.
+    pub weights: Option<Vec<f64>>,
+
+    /// Retain further points where needed so that the simplified curve doesn't cross itself,
+    /// when it didn't originally, in the plane of the first two coordinates.
+    pub preserve_topology: bool,
+
+    /// Process independent ranges, and the distances within large ranges, in parallel. The
+    /// result is identical to the serial algorithm. Ignored without the `rayon` feature.
+    pub parallel: bool,
+}
+
+
+/// Simplifies a curve using the Ramer-Douglas-Peucker algorithm with the given options.
+pub fn rdp_with_options<F: CurveFloat>(points: ArrayView2<'_, F>, epsilon: F, options: &RdpOptions) -> Array1<bool> {
+    if !options.preserve_topology {
+        return simplify_points(points, epsilon, options);
+    }
+
+    // Close rings explicitly, so that the closing segment is checked too.
+    let len = points.len_of(Axis(0));
+    if options.closed && len >= 4 && points.row(0) != points.row(len - 1) {
+        let ring = concatenate![Axis(0), points, points.slice(s![..1, ..])];
+        return rdp_with_options(ring.view(), epsilon, options).slice_move(s![..len]);
+    }
+
+    // Split any segment that crosses another at its furthest point, as RDP would have if the
+    // tolerance were smaller.
+    let unit_vectors = match options.metric {
+        DistanceMetric::Geographic(_) => Some(geodesic::unit_vectors(points)),
+        _ => None,
+    };
+    let metric_points = match &unit_vectors {
+        Some(unit_vectors) => unit_vectors.view(),
+        None => points.view(),
+    };
+    let weights = weights(options);
+    let weights = weights.as_ref().map(|weights| weights.view());
+    let mut mask = rdp_points(metric_points, epsilon, options);
+    topology::untangle(points, &mut mask, |start, end| {
+        let range = metric_points.slice(s![start..=end, ..]);
+        match furthest_point(range, &LineStartPointBuffer::from_points(range, weights), options.metric) {
+            (0, _) => (start + end) / 2,
+            (i, _) => start + i,
+        }
+    });
+
+    mask
+}
+
+
+fn simplify_points<F: CurveFloat>(points: ArrayView2<'_, F>, epsilon: F, options: &RdpOptions) -> Array1<bool> {
+    // Geographic coordinates are converted to points on the unit sphere, from which the
+    // distances over the surface are calculated.
+    if let DistanceMetric::Geographic(_) = options.metric {
+        return rdp_points(geodesic::unit_vectors(points).view(), epsilon, options);
+    }
+
+    rdp_points(points, epsilon, options)
+}
+
+
+fn rdp_points<F: CurveFloat>(points: ArrayView2<'_, F>, epsilon: F, options: &RdpOptions) -> Array1<bool> {
+    if options.closed {
+        return rdp_closed(points, epsilon, options);
+    }
 
-pub fn rdp(points: ArrayView2<'_, f64>, epsilon: f64) -> Array1<bool> {
     // Generate a mask boolean array, which will be the result.
     let mut mask = Array1::from_elem((points.len_of(Axis(0)),), false);
     mask[0] = true;
     let mask_len = mask.len();
     mask[mask_len - 1] = true;
 
-    // Run the recursive RDP algorithm
-    rdp_recurse(points, None, mask.view_mut(), epsilon.powi(2));
+    // Run the RDP algorithm
+    retain_splits(points, epsilon, options, vec![(0, mask_len - 1)], &mut mask);
+
+    mask
+}
+
+
+fn retain_splits<F: CurveFloat>(points: ArrayView2<'_, F>, epsilon: F, options: &RdpOptions, ranges: Vec<(usize, usize)>, mask: &mut Array1<bool>) {
+    // Retain any point further than epsilon from the line joining the endpoints of its range.
+    let epsilon_2 = epsilon.powi(2);
+
+    let weights = weights(options);
+    let weights = weights.as_ref().map(|weights| weights.view());
+
+    #[cfg(feature = "rayon")]
+    if options.parallel {
+        for i in parallel::par_rdp_splits(points, weights, options.metric, ranges, epsilon_2) {
+            mask[i] = true;
+        }
+        return;
+    }
+
+    let ranges = ranges.into_iter().map(|(start, end)| (start, end, ())).collect();
+    rdp_traverse(points, weights, options.metric, ranges, |i, d_2, ()| {
+        if d_2 > epsilon_2 {
+            mask[i] = true;
+            Some(())
+        } else {
+            None
+        }
+    });
+}
+
+
+fn rdp_closed<F: CurveFloat>(points: ArrayView2<'_, F>, epsilon: F, options: &RdpOptions) -> Array1<bool> {
+    let len = points.len_of(Axis(0));
+    if len < 4 {
+        return Array1::from_elem((len,), true);
+    }
+
+    // Close the ring explicitly if it isn't already, and drop the extra point from the result.
+    if points.row(0) != points.row(len - 1) {
+        let ring = concatenate![Axis(0), points, points.slice(s![..1, ..])];
+        return rdp_closed(ring.view(), epsilon, options).slice_move(s![..len]);
+    }
+
+    // Split the ring at the point furthest from the start, so that neither half has coincident
+    // endpoints.
+    let weights = weights(options);
+    let weights = weights.as_ref().map(|weights| weights.view());
+    let buffer = LineStartPointBuffer::from_points(points, weights);
+    let far = buffer.magnitudes_2.iter().enumerate()
+        .fold(0, |far, (i, &d_2)| if d_2 > buffer.magnitudes_2[far] { i } else { far });
+
+    let mut mask = Array1::from_elem((len,), false);
+    mask[0] = true;
+    mask[far] = true;
+    mask[len - 1] = true;
+
+    retain_splits(points, epsilon, options, vec![(far, len - 1), (0, far)], &mut mask);
+
+    // If neither half was split the ring has collapsed to a line, so retain the furthest point
+    // from it to keep the ring valid.
+    if mask.iter().filter(|&&keep| keep).count() < 4 {
+        let first = points.slice(s![..=far, ..]);
+        let second = points.slice(s![far.., ..]);
+        let (i_first, d_2_first) = furthest_point(first, &buffer.subset(Slice::from(..=far)), options.metric);
+        let (i_second, d_2_second) = furthest_point(second, &LineStartPointBuffer::from_points(second, weights), options.metric);
+        let i = if d_2_first >= d_2_second { i_first } else { far + i_second };
+
+        // If every point is on the line, any other point will do.
+        let i = if mask[i] { mask.iter().position(|&keep| !keep).unwrap_or(i) } else { i };
+        mask[i] = true;
+    }
+
+    mask
+}
+
+
+/// Simplifies a curve using the Ramer-Douglas-Peucker algorithm, after validating the inputs.
+pub fn try_rdp<F: CurveFloat>(points: ArrayView2<'_, F>, epsilon: F) -> Result<Array1<bool>, CurvedError> {
+    try_rdp_with_options(points, epsilon, &RdpOptions::default())
+}
+
+
+/// Simplifies a curve using the Ramer-Douglas-Peucker algorithm with the given options, after
+/// validating the inputs.
+pub fn try_rdp_with_options<F: CurveFloat>(points: ArrayView2<'_, F>, epsilon: F, options: &RdpOptions) -> Result<Array1<bool>, CurvedError> {
+    check_points(points)?;
+    check_options(points, epsilon, options)?;
+
+    Ok(rdp_with_options(points, epsilon, options))
+}
+
+
+pub(crate) fn check_options<F: CurveFloat>(points: ArrayView2<'_, F>, epsilon: F, options: &RdpOptions) -> Result<(), CurvedError> {
+    if epsilon.is_nan() || epsilon < F::zero() {
+        return Err(CurvedError::InvalidEpsilon(epsilon.to_f64().unwrap()));
+    }
+    let required = match options.metric {
+        DistanceMetric::Geographic(_) => 2,
+        DistanceMetric::Synchronized { time } => time.saturating_add(1),
+        _ => 0,
+    };
+    let required = if options.preserve_topology { required.max(2) } else { required };
+    if points.len_of(Axis(1)) < required {
+        return Err(CurvedError::TooFewDimensions(required));
+    }
+    if let Some(weights) = &options.weights {
+        let valid = weights.len() == points.len_of(Axis(1))
+            && weights.iter().all(|w| w.is_finite() && *w >= 0.0)
+            && !matches!(options.metric, DistanceMetric::Geographic(_));
+        if !valid {
+            return Err(CurvedError::InvalidWeights);
+        }
+    }
+    Ok(())
+}
+
+
+pub(crate) fn check_points<F: CurveFloat>(points: ArrayView2<'_, F>) -> Result<(), CurvedError> {
+    if points.len_of(Axis(0)) == 0 {
+        return Err(CurvedError::EmptyInput);
+    }
+    if points.len_of(Axis(1)) == 0 {
+        return Err(CurvedError::ZeroDimensional);
+    }
+    match points.outer_iter().position(|point| !point.iter().all(|v| v.is_finite())) {
+        Some(i) => Err(CurvedError::NonFiniteCoordinate(i)),
+        None => Ok(()),
+    }
+}
+
+
+/// Simplifies a curve to the `n_points` most significant points using the RDP algorithm.
+///
+/// Rather than recursing to a fixed tolerance, the segment with the furthest outlying point is
+/// split next, until `n_points` points are retained. The endpoints are always retained.
+pub fn rdp_count<F: CurveFloat>(points: ArrayView2<'_, F>, n_points: usize) -> Array1<bool> {
+    let len = points.len_of(Axis(0));
+    if len <= n_points.max(2) {
+        return Array1::from_elem((len,), true);
+    }
+
+    let mut mask = Array1::from_elem((len,), false);
+    mask[0] = true;
+    mask[len - 1] = true;
+
+    // Repeatedly split the segment containing the point that is furthest from its line.
+    let mut heap = BinaryHeap::new();
+    heap.push(Split::new(points, 0, len - 1));
+    let mut retained = 2;
+    while retained < n_points {
+        let split = match heap.pop() {
+            Some(split) if split.index > split.start => split,
+            _ => break,
+        };
+        mask[split.index] = true;
+        retained += 1;
+        heap.push(Split::new(points, split.start, split.index));
+        heap.push(Split::new(points, split.index, split.end));
+    }
 
     mask
 }
 
 
-struct LineStartPointBuffer<'a> {
-    vectors: CowArray<'a, f64, Ix2>,
-    magnitudes_2: CowArray<'a, f64, Ix1>,
+/// Calculates the significance of each point of a curve under the RDP algorithm.
+///
+/// The result holds, for each point, the largest epsilon at which it is retained by [`rdp`],
+/// so `rdp(points, epsilon)` is equivalent to `rdp_importance(points).mapv(|i| i > epsilon)`.
+/// The endpoints are always retained and so have an infinite significance.
+pub fn rdp_importance<F: CurveFloat>(points: ArrayView2<'_, F>) -> Array1<F> {
+    let len = points.len_of(Axis(0));
+    let mut importance = Array1::zeros((len,));
+    if len > 0 {
+        importance[0] = F::infinity();
+        importance[len - 1] = F::infinity();
+
+        // A point is only retained while its parent is, so it can be no more significant than
+        // it. Points lying on the line are never retained.
+        rdp_traverse(points, None, DistanceMetric::Line, vec![(0, len - 1, F::infinity())], |i, d_2, parent| {
+            if d_2 > F::zero() {
+                importance[i] = d_2.sqrt().min(parent);
+                Some(importance[i])
+            } else {
+                None
+            }
+        });
+    }
+
+    importance
+}
+
+
+struct Split {
+    distance_2: f64,
+    start: usize,
+    index: usize,
+    end: usize,
+}
+
+
+impl Split {
+    fn new<F: CurveFloat>(points: ArrayView2<'_, F>, start: usize, end: usize) -> Split {
+        let section = points.slice(s![start..=end, ..]);
+        let buffer = LineStartPointBuffer::from_points(section, None);
+        let (i_max, distance_2) = furthest_point(section, &buffer, DistanceMetric::Line);
+        Split { distance_2: distance_2.to_f64().unwrap(), start, index: start + i_max, end }
+    }
+}
+
+
+impl PartialEq for Split {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+
+impl Eq for Split {}
+
+
+impl PartialOrd for Split {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+
+impl Ord for Split {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Furthest point first, with ties broken by position along the curve.
+        self.distance_2.total_cmp(&other.distance_2).then_with(|| other.start.cmp(&self.start))
+    }
+}
+
+
+struct LineStartPointBuffer<'a, F> {
+    vectors: CowArray<'a, F, Ix2>,
+    magnitudes_2: CowArray<'a, F, Ix1>,
+    weights: Option<ArrayView1<'a, F>>,
 }
 
 
-impl LineStartPointBuffer<'_> {
-    fn from_points<'a>(points: ArrayView2<f64>) -> LineStartPointBuffer<'a> {
+impl<'a, F: CurveFloat> LineStartPointBuffer<'a, F> {
+    fn from_points(points: ArrayView2<F>, weights: Option<ArrayView1<'a, F>>) -> LineStartPointBuffer<'a, F> {
         let start = points.slice(s![0, ..]);
-        let vectors = &points - &start;
+        let mut vectors = &points - &start;
+        if let Some(weights) = weights {
+            vectors = vectors * weights;
+        }
         let magnitudes_2 = (&vectors * &vectors).sum_axis(Axis(1));
-        LineStartPointBuffer { vectors: vectors.into(), magnitudes_2: magnitudes_2.into() }
+        LineStartPointBuffer { vectors: vectors.into(), magnitudes_2: magnitudes_2.into(), weights }
     }
 
-    fn subset(&self, slice: Slice) -> LineStartPointBuffer {
+    fn subset(&self, slice: Slice) -> LineStartPointBuffer<'_, F> {
         LineStartPointBuffer {vectors: self.vectors.slice(s![slice, ..]).into(),
-            magnitudes_2: self.magnitudes_2.slice(s![slice]).into()}
+            magnitudes_2: self.magnitudes_2.slice(s![slice]).into(),
+            weights: self.weights.map(|weights| weights.reborrow())}
     }
 }
 
 
-fn line_point_distances_2(
-    start: ArrayView1<'_, f64>,
-    end: ArrayView1<'_, f64>,
-    buffer: &LineStartPointBuffer<'_>
-) -> Array1<f64> {
+fn weights<F: CurveFloat>(options: &RdpOptions) -> Option<Array1<F>> {
+    options.weights.as_ref().map(|weights| weights.iter().map(|&w| F::from(w).unwrap()).collect())
+}
+
+
+fn line_point_distances_2<F: CurveFloat>(
+    start: ArrayView1<'_, F>,
+    end: ArrayView1<'_, F>,
+    buffer: &LineStartPointBuffer<'_, F>,
+    metric: DistanceMetric
+) -> Array1<F> {
+    match metric {
+        DistanceMetric::Geographic(ellipsoid) => {
+            return geodesic::cross_track_distances_2(start, end, buffer, ellipsoid);
+        },
+        DistanceMetric::Synchronized { time } => {
+            return trajectory::synchronized_distances_2(start, end, buffer, time);
+        },
+        _ => (),
+    }
+
     // Calculate the unit vector from the start (A) to the end (B)
-    let ab: Array1<f64> = &end - &start;
+    let ab = line_vector(start, end, buffer);
     let ab_magnitude = ab.dot(&ab).sqrt();
+
+    // If the start and end coincide there is no line, so use the distance from the start.
+    if ab_magnitude.is_zero() {
+        return buffer.magnitudes_2.to_owned();
+    }
     let ab_unit = ab / ab_magnitude;
 
     // Project the point (C) to start (A) vector onto the unit vector using a dot product.
     // It should return the magnitude of the vector from the start (A) to the point closest
     // to the AB line (D).
-    let ad_magnitudes_2 = buffer.vectors.dot(&ab_unit).mapv(|v| v.powi(2));
-    &buffer.magnitudes_2 - &ad_magnitudes_2
+    let mut distances_2 = buffer.vectors.dot(&ab_unit);
+    distances_2.zip_mut_with(&buffer.magnitudes_2, |ad, &ac_2| {
+        *ad = point_distance_2(*ad, ac_2, ab_magnitude, metric);
+    });
+    distances_2
 }
 
-fn rdp_recurse(points: ArrayView2<'_, f64>, buffer: Option<LineStartPointBuffer<'_>>, mut mask: ArrayViewMut1<'_, bool>, epsilon_2: f64) {
+fn line_vector<F: CurveFloat>(start: ArrayView1<'_, F>, end: ArrayView1<'_, F>, buffer: &LineStartPointBuffer<'_, F>) -> Array1<F> {
+    // The vector from the start to the end, scaled in the same way as the buffered vectors.
+    let mut ab = &end - &start;
+    if let Some(weights) = buffer.weights {
+        ab = ab * weights;
+    }
+    ab
+}
+
+fn point_distance_2<F: CurveFloat>(ad: F, ac_2: F, ab_magnitude: F, metric: DistanceMetric) -> F {
+    match metric {
+        DistanceMetric::Line => ac_2 - ad.powi(2),
+        // Points that project before the start (A) are measured to A, and those that project
+        // beyond the end (B) are measured to B.
+        DistanceMetric::Segment => if ad < F::zero() {
+            ac_2
+        } else if ad > ab_magnitude {
+            ac_2 - (ad + ad) * ab_magnitude + ab_magnitude.powi(2)
+        } else {
+            ac_2 - ad.powi(2)
+        },
+        DistanceMetric::Geographic(_) | DistanceMetric::Synchronized { .. } => {
+            unreachable!("{:?} distances are calculated separately", metric)
+        },
+    }
+}
+
+fn furthest_point<F: CurveFloat>(points: ArrayView2<'_, F>, buffer: &LineStartPointBuffer<'_, F>, metric: DistanceMetric) -> (usize, F) {
     // Get the start and end points of the curve
     let start = points.slice(s![0, ..]);
     let end = points.slice(s![-1, ..]);
 
-    // Calculate some buffered line start to point values.
-    let buffer = buffer.unwrap_or_else(|| LineStartPointBuffer::from_points(points));
-
     // Calculate the distance squared between the line and each point.
-    let distances_2 = line_point_distances_2(start, end, &buffer);
+    let distances_2 = line_point_distances_2(start, end, buffer, metric);
+    max_distance(&distances_2)
+}
 
+fn max_distance<F: CurveFloat>(distances_2: &Array1<F>) -> (usize, F) {
     // Find the point with the maximum distance from the line joining the endpoints.
-    let mut d_2_max = 0.0;
+    let mut d_2_max = F::zero();
     let mut i_max: usize = 0;
     for (i, d) in distances_2.iter().enumerate().take(distances_2.len() - 1).skip(1) {
         if d > &d_2_max {
@@ -76,32 +520,34 @@ fn rdp_recurse(points: ArrayView2<'_, f64>, buffer: Option<LineStartPointBuffer<
         }
     }
 
-    // If that point is further away from the line joining the endpoints than epsilon^2,
-    // mark it as retained and recurse the algorithm for the line joining start->point
-    // and point->end.
-    if d_2_max > epsilon_2 {
-        mask[i_max] = true;
-        rdp_recurse(points.slice(s![..=i_max, ..]),
-            Some(buffer.subset(Slice::from(..=i_max))),
-            mask.slice_mut(s![..=i_max]),
-            epsilon_2);
-        rdp_recurse(points.slice(s![i_max.., ..]), None, mask.slice_mut(s![i_max..]), epsilon_2);
-    }
+    (i_max, d_2_max)
 }
 
-#[pymodule]
-fn _rustlib(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
-    #[pyfunction]
-    fn rdp(
-        py: Python<'_>,
-        points: &PyArray2<f64>,
-        epsilon: f64
-    ) -> Py<PyArray1<bool>> {
-        let points = points.readonly();
-        crate::rdp(points.as_array(), epsilon).into_pyarray(py).to_owned()
-    }
+fn rdp_traverse<F: CurveFloat, T: Copy>(points: ArrayView2<'_, F>, weights: Option<ArrayView1<'_, F>>, metric: DistanceMetric, mut stack: Vec<(usize, usize, T)>, mut split: impl FnMut(usize, F, T) -> Option<T>) {
+    // Ranges still to be processed are kept on an explicit stack rather than recursing, so that
+    // the depth of the splits is not limited by the size of the call stack.
+    while let Some((start, mut end, mut state)) = stack.pop() {
+        // Calculate some buffered line start to point values. Every range to the left of a split
+        // shares the same start point, so the buffer is reused for them.
+        let buffer = LineStartPointBuffer::from_points(points.slice(s![start..=end, ..]), weights);
 
-    m.add_function(wrap_pyfunction!(rdp, m)?)?;
+        loop {
+            // Find the point with the maximum distance from the line joining the endpoints.
+            let (i_max, d_2_max) = furthest_point(
+                points.slice(s![start..=end, ..]),
+                &buffer.subset(Slice::from(..=end - start)),
+                metric);
 
-    Ok(())
+            // If the point is to be retained, the range start->point is processed next, and
+            // point->end is deferred until after it.
+            match split(start + i_max, d_2_max, state) {
+                Some(child) => {
+                    stack.push((start + i_max, end, child));
+                    end = start + i_max;
+                    state = child;
+                },
+                None => break,
+            }
+        }
+    }
 }
@@ -0,0 +1,111 @@
+use std::sync::Mutex;
+
+use ndarray::{s, Array1, ArrayView1, ArrayView2, Slice, Zip};
+use rayon::Scope;
+
+use crate::{line_vector, max_distance, point_distance_2, rdp_with_options, CurveFloat, DistanceMetric, LineStartPointBuffer, RdpOptions};
+
+
+// Ranges with fewer points than these are not worth the overhead of handing to another thread.
+const MIN_PARALLEL_RANGE: usize = 1 << 12;
+const MIN_PARALLEL_DISTANCES: usize = 1 << 14;
+
+
+/// Simplifies a curve using the Ramer-Douglas-Peucker algorithm, processing independent ranges
+/// in parallel. The result is identical to [`rdp`](crate::rdp).
+pub fn par_rdp<F: CurveFloat>(points: ArrayView2<'_, F>, epsilon: F) -> Array1<bool> {
+    rdp_with_options(points, epsilon, &RdpOptions { parallel: true, ..Default::default() })
+}
+
+
+pub(crate) fn par_rdp_splits<F: CurveFloat>(
+    points: ArrayView2<'_, F>,
+    weights: Option<ArrayView1<'_, F>>,
+    metric: DistanceMetric,
+    ranges: Vec<(usize, usize)>,
+    epsilon_2: F
+) -> Vec<usize> {
+    let retained = Mutex::new(Vec::new());
+    rayon::scope(|scope| {
+        for (start, end) in ranges {
+            let retained = &retained;
+            scope.spawn(move |scope| par_rdp_range(scope, points, weights, metric, start, end, epsilon_2, retained));
+        }
+    });
+    retained.into_inner().unwrap()
+}
+
+
+#[allow(clippy::too_many_arguments)]
+fn par_rdp_range<'s, 'a: 's, 'w: 's, 'r: 's, F: CurveFloat>(
+    scope: &Scope<'s>,
+    points: ArrayView2<'a, F>,
+    weights: Option<ArrayView1<'w, F>>,
+    metric: DistanceMetric,
+    start: usize,
+    end: usize,
+    epsilon_2: F,
+    retained: &'r Mutex<Vec<usize>>
+) {
+    // This follows the serial traversal, except that large ranges to the right of a split are
+    // spawned as separate tasks rather than deferred.
+    let mut splits = Vec::new();
+    let mut stack = vec![(start, end)];
+    while let Some((start, mut end)) = stack.pop() {
+        let buffer = LineStartPointBuffer::from_points(points.slice(s![start..=end, ..]), weights);
+
+        loop {
+            let section = points.slice(s![start..=end, ..]);
+            let distances_2 = par_line_point_distances_2(
+                section.slice(s![0, ..]),
+                section.slice(s![-1, ..]),
+                &buffer.subset(Slice::from(..=end - start)),
+                metric);
+            let (i_max, d_2_max) = max_distance(&distances_2);
+            if d_2_max <= epsilon_2 {
+                break;
+            }
+
+            let split = start + i_max;
+            splits.push(split);
+            if end - split >= MIN_PARALLEL_RANGE {
+                scope.spawn(move |scope| par_rdp_range(scope, points, weights, metric, split, end, epsilon_2, retained));
+            } else {
+                stack.push((split, end));
+            }
+            end = split;
+        }
+    }
+
+    retained.lock().unwrap().extend(splits);
+}
+
+
+fn par_line_point_distances_2<F: CurveFloat>(
+    start: ArrayView1<'_, F>,
+    end: ArrayView1<'_, F>,
+    buffer: &LineStartPointBuffer<'_, F>,
+    metric: DistanceMetric
+) -> Array1<F> {
+    if buffer.magnitudes_2.len() < MIN_PARALLEL_DISTANCES || matches!(metric, DistanceMetric::Geographic(_) | DistanceMetric::Synchronized { .. }) {
+        return crate::line_point_distances_2(start, end, buffer, metric);
+    }
+
+    // Calculate the unit vector from the start (A) to the end (B)
+    let ab = line_vector(start, end, buffer);
+    let ab_magnitude = ab.dot(&ab).sqrt();
+    if ab_magnitude.is_zero() {
+        return buffer.magnitudes_2.to_owned();
+    }
+    let ab_unit = ab / ab_magnitude;
+
+    // Each point is handled exactly as the serial path does, so the results are bit-identical.
+    let mut distances_2 = Array1::zeros(buffer.magnitudes_2.raw_dim());
+    Zip::from(&mut distances_2)
+        .and(buffer.vectors.rows())
+        .and(&buffer.magnitudes_2)
+        .par_for_each(|d_2, ac, &ac_2| {
+            *d_2 = point_distance_2(ac.dot(&ab_unit), ac_2, ab_magnitude, metric);
+        });
+    distances_2
+}
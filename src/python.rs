@@ -0,0 +1,479 @@
+use std::convert::TryFrom;
+
+use ndarray::{Axis, Array1, ArrayView1, ArrayView2};
+use numpy::{convert::IntoPyArray, Element, PyArray1, PyArray2};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::{pyclass, pymethods, pymodule, pyfunction, pyproto, wrap_pyfunction, FromPyObject, IntoPy, Py, PyErr, PyModule, PyObject, PyRef, PyRefMut, PyResult, Python};
+use pyo3::PyIterProtocol;
+
+use crate::{
+    check_options, check_points, geometry, rdp_count, rdp_with_options, strip, try_rdp_batch,
+    try_rdp_shared, try_rdp_with_options, CurveFloat, CurvedError, DistanceMetric, Ellipsoid,
+    RdpOptions, StreamingSimplifier, DEFAULT_MAX_WINDOW,
+};
+
+
+impl From<CurvedError> for PyErr {
+    fn from(error: CurvedError) -> PyErr {
+        PyValueError::new_err(error.to_string())
+    }
+}
+
+#[cfg(feature = "geojson")]
+impl From<crate::GeoJsonError> for PyErr {
+    fn from(error: crate::GeoJsonError) -> PyErr {
+        PyValueError::new_err(error.to_string())
+    }
+}
+
+#[derive(FromPyObject)]
+enum FloatArray2<'py> {
+    F32(&'py PyArray2<f32>),
+    F64(&'py PyArray2<f64>),
+}
+
+fn rdp_mask<F: CurveFloat + Element>(
+    py: Python<'_>,
+    points: &PyArray2<F>,
+    epsilon: Option<f64>,
+    n_points: Option<usize>,
+    options: &RdpOptions
+) -> PyResult<Array1<bool>> {
+    let points = points.readonly();
+    let points = points.as_array();
+
+    // The simplification only reads the points, so other Python threads can run meanwhile.
+    match (epsilon, n_points) {
+        (Some(epsilon), None) => {
+            let epsilon = F::from(epsilon).unwrap();
+            Ok(py.allow_threads(|| try_rdp_with_options(points, epsilon, options))?)
+        },
+        (None, Some(_)) if options.closed || options.metric != DistanceMetric::Line || options.weights.is_some() || options.preserve_topology => {
+            Err(PyValueError::new_err("closed, metric, weights and preserve_topology are not supported with n_points"))
+        },
+        (None, Some(n_points)) => {
+            Ok(py.allow_threads(|| check_points(points).map(|_| rdp_count(points, n_points)))?)
+        },
+        _ => Err(PyValueError::new_err("exactly one of epsilon or n_points must be given")),
+    }
+}
+
+fn rdp_importance_array<F: CurveFloat + Element>(py: Python<'_>, points: &PyArray2<F>) -> PyResult<PyObject> {
+    let points = points.readonly();
+    let points = points.as_array();
+    let importance = py.allow_threads(|| check_points(points).map(|_| crate::rdp_importance(points)))?;
+    Ok(importance.into_pyarray(py).into_py(py))
+}
+
+#[derive(FromPyObject)]
+enum OffsetArray<'py> {
+    I32(&'py PyArray1<i32>),
+    I64(&'py PyArray1<i64>),
+}
+
+impl OffsetArray<'_> {
+    fn to_vec(&self) -> PyResult<Vec<usize>> {
+        let offsets: Option<Vec<usize>> = match self {
+            OffsetArray::I32(offsets) => offsets.readonly().as_array().iter().map(|&o| usize::try_from(o).ok()).collect(),
+            OffsetArray::I64(offsets) => offsets.readonly().as_array().iter().map(|&o| usize::try_from(o).ok()).collect(),
+        };
+        offsets.ok_or_else(|| CurvedError::InvalidOffsets.into())
+    }
+}
+
+fn rdp_batch_mask<F: CurveFloat + Element>(
+    py: Python<'_>,
+    points: &PyArray2<F>,
+    offsets: &[usize],
+    epsilon: f64,
+    options: &RdpOptions
+) -> PyResult<Array1<bool>> {
+    let points = points.readonly();
+    let points = points.as_array();
+    let epsilon = F::from(epsilon).unwrap();
+    Ok(py.allow_threads(|| try_rdp_batch(points, offsets, epsilon, options))?)
+}
+
+fn rdp_part<F: CurveFloat + Element>(
+    py: Python<'_>,
+    part: &PyArray2<F>,
+    epsilon: f64,
+    ring: bool,
+    options: &RdpOptions
+) -> PyResult<PyObject> {
+    let points = part.readonly();
+    let points = points.as_array();
+    let epsilon = F::from(epsilon).unwrap();
+    let simplified = py.allow_threads(|| {
+        if points.len_of(Axis(0)) == 0 {
+            return Ok(points.to_owned());
+        }
+        check_points(points)?;
+        check_options(points, epsilon, options)?;
+        Ok::<_, CurvedError>(if ring {
+            geometry::rdp_ring(points, epsilon, options)
+        } else {
+            geometry::retained(points, &rdp_with_options(points, epsilon, options))
+        })
+    })?;
+    Ok(simplified.into_pyarray(py).into_py(py))
+}
+
+fn rdp_parts(py: Python<'_>, parts: Vec<FloatArray2<'_>>, epsilon: f64, ring: bool, options: &RdpOptions) -> PyResult<Vec<PyObject>> {
+    parts.into_iter()
+        .map(|part| match part {
+            FloatArray2::F32(part) => rdp_part(py, part, epsilon, ring, options),
+            FloatArray2::F64(part) => rdp_part(py, part, epsilon, ring, options),
+        })
+        .collect()
+}
+
+fn rdp_shared_mask<F: CurveFloat + Element>(
+    py: Python<'_>,
+    points: &PyArray2<F>,
+    offsets: &[usize],
+    epsilon: f64,
+    options: &RdpOptions
+) -> PyResult<Array1<bool>> {
+    let points = points.readonly();
+    let points = points.as_array();
+    let epsilon = F::from(epsilon).unwrap();
+    Ok(py.allow_threads(|| try_rdp_shared(points, offsets, epsilon, options))?)
+}
+
+/// Validates the points and the tolerance called `name`, then simplifies the points with it.
+fn tolerance_mask<F, S>(py: Python<'_>, points: &PyArray2<F>, name: &'static str, tolerance: f64, simplify: S) -> PyResult<Array1<bool>>
+where
+    F: CurveFloat + Element,
+    S: FnOnce(ArrayView2<'_, F>, F) -> Array1<bool> + Send,
+{
+    let points = points.readonly();
+    let points = points.as_array();
+    let tolerance = F::from(tolerance).unwrap();
+    Ok(py.allow_threads(|| {
+        check_points(points)?;
+        check_options(points, tolerance, &RdpOptions::default()).map_err(|error| match error {
+            CurvedError::InvalidEpsilon(value) => CurvedError::InvalidTolerance(name, value),
+            error => error,
+        })?;
+        Ok::<_, CurvedError>(simplify(points, tolerance))
+    })?)
+}
+
+fn visvalingam_mask<F: CurveFloat + Element>(py: Python<'_>, points: &PyArray2<F>, min_area: f64) -> PyResult<Array1<bool>> {
+    tolerance_mask(py, points, "min_area", min_area, |points, min_area| crate::visvalingam(points, min_area))
+}
+
+fn imai_iri_mask<F: CurveFloat + Element>(py: Python<'_>, points: &PyArray2<F>, epsilon: f64) -> PyResult<Array1<bool>> {
+    tolerance_mask(py, points, "epsilon", epsilon, |points, epsilon| crate::imai_iri(points, epsilon))
+}
+
+fn lang_mask<F: CurveFloat + Element>(py: Python<'_>, points: &PyArray2<F>, epsilon: f64, look_ahead: usize) -> PyResult<Array1<bool>> {
+    tolerance_mask(py, points, "epsilon", epsilon, |points, epsilon| crate::lang(points, epsilon, look_ahead))
+}
+
+fn strip_mask<F: CurveFloat + Element>(py: Python<'_>, points: &PyArray2<F>, tolerance: f64, kind: strip::StripKind) -> PyResult<Array1<bool>> {
+    if let strip::StripKind::Opheim { max_distance } = kind {
+        strip::check_max_distance(max_distance)?;
+    }
+    tolerance_mask(py, points, "tolerance", tolerance, |points, tolerance| strip::simplify_strip(points, tolerance, kind))
+}
+
+/// Simplifies a curve whose points arrive one at a time, with the same tolerance as `rdp`.
+#[pyclass(name = "StreamingSimplifier")]
+struct PyStreamingSimplifier {
+    simplifier: StreamingSimplifier<f64>,
+}
+
+#[pymethods]
+impl PyStreamingSimplifier {
+    #[new]
+    #[args(epsilon, "*", max_window = "DEFAULT_MAX_WINDOW")]
+    fn new(epsilon: f64, max_window: usize) -> PyResult<Self> {
+        if epsilon.is_nan() || epsilon < 0.0 {
+            return Err(CurvedError::InvalidEpsilon(epsilon).into());
+        }
+        Ok(PyStreamingSimplifier { simplifier: StreamingSimplifier::with_max_window(epsilon, max_window) })
+    }
+
+    fn push(&mut self, point: Vec<f64>) -> PyResult<()> {
+        Ok(self.simplifier.push(ArrayView1::from(&point))?)
+    }
+
+    fn flush(&mut self) {
+        self.simplifier.flush();
+    }
+}
+
+#[pyproto]
+impl PyIterProtocol for PyStreamingSimplifier {
+    fn __iter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<Self>) -> Option<PyObject> {
+        let retained = slf.simplifier.next();
+        let py = slf.py();
+        retained.map(|(index, point)| (index, point.into_pyarray(py)).into_py(py))
+    }
+}
+
+fn parse_metric(metric: &str, time_index: usize) -> PyResult<DistanceMetric> {
+    match metric {
+        "line" => Ok(DistanceMetric::Line),
+        "segment" => Ok(DistanceMetric::Segment),
+        "spherical" => Ok(DistanceMetric::Geographic(Ellipsoid::Sphere)),
+        "wgs84" => Ok(DistanceMetric::Geographic(Ellipsoid::Wgs84)),
+        "synchronized" => Ok(DistanceMetric::Synchronized { time: time_index }),
+        _ => Err(PyValueError::new_err(format!("unknown metric {:?}", metric))),
+    }
+}
+
+#[cfg(feature = "geojson")]
+fn parse_algorithm(algorithm: &str, metric: &str, look_ahead: Option<usize>, max_distance: Option<f64>) -> PyResult<crate::Algorithm> {
+    let required = |name: &str| PyValueError::new_err(format!("{} is required by {}", name, algorithm));
+    if algorithm != "rdp" && metric != "line" {
+        return Err(PyValueError::new_err(format!("metric {:?} is only supported by rdp", metric)));
+    }
+    match algorithm {
+        "rdp" => Ok(crate::Algorithm::Rdp(RdpOptions { metric: parse_metric(metric, 0)?, ..Default::default() })),
+        "visvalingam" => Ok(crate::Algorithm::Visvalingam),
+        "imai_iri" => Ok(crate::Algorithm::ImaiIri),
+        "lang" => Ok(crate::Algorithm::Lang { look_ahead: look_ahead.ok_or_else(|| required("look_ahead"))? }),
+        "reumann_witkam" => Ok(crate::Algorithm::ReumannWitkam),
+        "opheim" => Ok(crate::Algorithm::Opheim { max_distance: max_distance.ok_or_else(|| required("max_distance"))? }),
+        _ => Err(PyValueError::new_err(format!("unknown algorithm {:?}", algorithm))),
+    }
+}
+
+#[pymodule]
+fn _rustlib(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    #[pyfunction(points, epsilon = "None", "*", n_points = "None", closed = "false", metric = "\"line\"", time_index = "0", weights = "None", preserve_topology = "false")]
+    #[allow(clippy::too_many_arguments)]
+    fn rdp(
+        py: Python<'_>,
+        points: FloatArray2<'_>,
+        epsilon: Option<f64>,
+        n_points: Option<usize>,
+        closed: bool,
+        metric: &str,
+        time_index: usize,
+        weights: Option<Vec<f64>>,
+        preserve_topology: bool
+    ) -> PyResult<Py<PyArray1<bool>>> {
+        let metric = parse_metric(metric, time_index)?;
+        let options = crate::RdpOptions { metric, closed, weights, preserve_topology, ..Default::default() };
+        let mask = match points {
+            FloatArray2::F32(points) => rdp_mask(py, points, epsilon, n_points, &options)?,
+            FloatArray2::F64(points) => rdp_mask(py, points, epsilon, n_points, &options)?,
+        };
+        Ok(mask.into_pyarray(py).to_owned())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[pyfunction(points, offsets, epsilon, "*", closed = "false", metric = "\"line\"", time_index = "0", weights = "None", preserve_topology = "false", parallel = "false")]
+    fn rdp_batch(
+        py: Python<'_>,
+        points: FloatArray2<'_>,
+        offsets: OffsetArray<'_>,
+        epsilon: f64,
+        closed: bool,
+        metric: &str,
+        time_index: usize,
+        weights: Option<Vec<f64>>,
+        preserve_topology: bool,
+        parallel: bool
+    ) -> PyResult<Py<PyArray1<bool>>> {
+        let offsets = offsets.to_vec()?;
+        let metric = parse_metric(metric, time_index)?;
+        let options = crate::RdpOptions { metric, closed, weights, preserve_topology, parallel };
+        let mask = match points {
+            FloatArray2::F32(points) => rdp_batch_mask(py, points, &offsets, epsilon, &options)?,
+            FloatArray2::F64(points) => rdp_batch_mask(py, points, &offsets, epsilon, &options)?,
+        };
+        Ok(mask.into_pyarray(py).to_owned())
+    }
+
+    #[pyfunction(points, offsets, epsilon, "*", metric = "\"line\"", time_index = "0", weights = "None")]
+    fn rdp_shared(
+        py: Python<'_>,
+        points: FloatArray2<'_>,
+        offsets: OffsetArray<'_>,
+        epsilon: f64,
+        metric: &str,
+        time_index: usize,
+        weights: Option<Vec<f64>>
+    ) -> PyResult<Py<PyArray1<bool>>> {
+        let offsets = offsets.to_vec()?;
+        let metric = parse_metric(metric, time_index)?;
+        let options = crate::RdpOptions { metric, weights, ..Default::default() };
+        let mask = match points {
+            FloatArray2::F32(points) => rdp_shared_mask(py, points, &offsets, epsilon, &options)?,
+            FloatArray2::F64(points) => rdp_shared_mask(py, points, &offsets, epsilon, &options)?,
+        };
+        Ok(mask.into_pyarray(py).to_owned())
+    }
+
+    #[pyfunction(rings, epsilon, "*", metric = "\"line\"", time_index = "0", weights = "None")]
+    fn rdp_polygon(
+        py: Python<'_>,
+        rings: Vec<FloatArray2<'_>>,
+        epsilon: f64,
+        metric: &str,
+        time_index: usize,
+        weights: Option<Vec<f64>>
+    ) -> PyResult<Vec<PyObject>> {
+        let metric = parse_metric(metric, time_index)?;
+        let options = crate::RdpOptions { metric, weights, ..Default::default() };
+        rdp_parts(py, rings, epsilon, true, &options)
+    }
+
+    #[pyfunction(polygons, epsilon, "*", metric = "\"line\"", time_index = "0", weights = "None")]
+    fn rdp_multipolygon(
+        py: Python<'_>,
+        polygons: Vec<Vec<FloatArray2<'_>>>,
+        epsilon: f64,
+        metric: &str,
+        time_index: usize,
+        weights: Option<Vec<f64>>
+    ) -> PyResult<Vec<Vec<PyObject>>> {
+        let metric = parse_metric(metric, time_index)?;
+        let options = crate::RdpOptions { metric, weights, ..Default::default() };
+        polygons.into_iter().map(|rings| rdp_parts(py, rings, epsilon, true, &options)).collect()
+    }
+
+    #[pyfunction(lines, epsilon, "*", metric = "\"line\"", time_index = "0", weights = "None")]
+    fn rdp_multilinestring(
+        py: Python<'_>,
+        lines: Vec<FloatArray2<'_>>,
+        epsilon: f64,
+        metric: &str,
+        time_index: usize,
+        weights: Option<Vec<f64>>
+    ) -> PyResult<Vec<PyObject>> {
+        let metric = parse_metric(metric, time_index)?;
+        let options = crate::RdpOptions { metric, weights, ..Default::default() };
+        rdp_parts(py, lines, epsilon, false, &options)
+    }
+
+    #[pyfunction]
+    fn rdp_importance(
+        py: Python<'_>,
+        points: FloatArray2<'_>
+    ) -> PyResult<PyObject> {
+        match points {
+            FloatArray2::F32(points) => rdp_importance_array(py, points),
+            FloatArray2::F64(points) => rdp_importance_array(py, points),
+        }
+    }
+
+    #[pyfunction]
+    fn visvalingam(
+        py: Python<'_>,
+        points: FloatArray2<'_>,
+        min_area: f64
+    ) -> PyResult<Py<PyArray1<bool>>> {
+        let mask = match points {
+            FloatArray2::F32(points) => visvalingam_mask(py, points, min_area)?,
+            FloatArray2::F64(points) => visvalingam_mask(py, points, min_area)?,
+        };
+        Ok(mask.into_pyarray(py).to_owned())
+    }
+
+    #[pyfunction]
+    fn imai_iri(
+        py: Python<'_>,
+        points: FloatArray2<'_>,
+        epsilon: f64
+    ) -> PyResult<Py<PyArray1<bool>>> {
+        let mask = match points {
+            FloatArray2::F32(points) => imai_iri_mask(py, points, epsilon)?,
+            FloatArray2::F64(points) => imai_iri_mask(py, points, epsilon)?,
+        };
+        Ok(mask.into_pyarray(py).to_owned())
+    }
+
+    #[pyfunction]
+    fn lang(
+        py: Python<'_>,
+        points: FloatArray2<'_>,
+        epsilon: f64,
+        look_ahead: usize
+    ) -> PyResult<Py<PyArray1<bool>>> {
+        let mask = match points {
+            FloatArray2::F32(points) => lang_mask(py, points, epsilon, look_ahead)?,
+            FloatArray2::F64(points) => lang_mask(py, points, epsilon, look_ahead)?,
+        };
+        Ok(mask.into_pyarray(py).to_owned())
+    }
+
+    #[pyfunction]
+    fn reumann_witkam(
+        py: Python<'_>,
+        points: FloatArray2<'_>,
+        tolerance: f64
+    ) -> PyResult<Py<PyArray1<bool>>> {
+        let kind = strip::StripKind::ReumannWitkam;
+        let mask = match points {
+            FloatArray2::F32(points) => strip_mask(py, points, tolerance, kind)?,
+            FloatArray2::F64(points) => strip_mask(py, points, tolerance, kind)?,
+        };
+        Ok(mask.into_pyarray(py).to_owned())
+    }
+
+    #[pyfunction]
+    fn opheim(
+        py: Python<'_>,
+        points: FloatArray2<'_>,
+        tolerance: f64,
+        max_distance: f64
+    ) -> PyResult<Py<PyArray1<bool>>> {
+        let kind = strip::StripKind::Opheim { max_distance };
+        let mask = match points {
+            FloatArray2::F32(points) => strip_mask(py, points, tolerance, kind)?,
+            FloatArray2::F64(points) => strip_mask(py, points, tolerance, kind)?,
+        };
+        Ok(mask.into_pyarray(py).to_owned())
+    }
+
+    #[cfg(feature = "geojson")]
+    #[pyfunction(geojson, epsilon, "*", algorithm = "\"rdp\"", metric = "\"line\"", look_ahead = "None", max_distance = "None")]
+    fn simplify_geojson(
+        py: Python<'_>,
+        geojson: &pyo3::PyAny,
+        epsilon: f64,
+        algorithm: &str,
+        metric: &str,
+        look_ahead: Option<usize>,
+        max_distance: Option<f64>
+    ) -> PyResult<PyObject> {
+        let algorithm = parse_algorithm(algorithm, metric, look_ahead, max_distance)?;
+        if let Ok(text) = geojson.extract::<String>() {
+            let simplified = py.allow_threads(|| crate::simplify_geojson(&text, epsilon, &algorithm))?;
+            return Ok(simplified.into_py(py));
+        }
+
+        let json = py.import("json")?;
+        let text: String = json.call_method1("dumps", (geojson,))?.extract()?;
+        let simplified = py.allow_threads(|| crate::simplify_geojson(&text, epsilon, &algorithm))?;
+        Ok(json.call_method1("loads", (simplified,))?.into_py(py))
+    }
+
+    m.add_function(wrap_pyfunction!(rdp, m)?)?;
+    m.add_function(wrap_pyfunction!(rdp_batch, m)?)?;
+    m.add_function(wrap_pyfunction!(rdp_shared, m)?)?;
+    m.add_function(wrap_pyfunction!(rdp_polygon, m)?)?;
+    m.add_function(wrap_pyfunction!(rdp_multipolygon, m)?)?;
+    m.add_function(wrap_pyfunction!(rdp_multilinestring, m)?)?;
+    m.add_function(wrap_pyfunction!(rdp_importance, m)?)?;
+    m.add_function(wrap_pyfunction!(visvalingam, m)?)?;
+    m.add_function(wrap_pyfunction!(imai_iri, m)?)?;
+    m.add_function(wrap_pyfunction!(lang, m)?)?;
+    m.add_function(wrap_pyfunction!(reumann_witkam, m)?)?;
+    m.add_function(wrap_pyfunction!(opheim, m)?)?;
+    #[cfg(feature = "geojson")]
+    m.add_function(wrap_pyfunction!(simplify_geojson, m)?)?;
+    m.add_class::<PyStreamingSimplifier>()?;
+
+    Ok(())
+}
@@ -0,0 +1,174 @@
+use std::collections::{HashMap, HashSet};
+
+use ndarray::{Axis, Array1, ArrayView2};
+
+use crate::batch::check_offsets;
+use crate::{
+    check_options, check_points, line_point_distances_2, rdp_with_options, weights, CurveFloat, CurvedError,
+    LineStartPointBuffer, RdpOptions,
+};
+
+
+/// Simplifies a collection of rings using the Ramer-Douglas-Peucker algorithm, keeping shared
+/// boundaries consistent.
+///
+/// The rings are concatenated in `points` with `offsets`, as in [`rdp_batch`], and may or may not
+/// repeat their first point at the end. As in TopoJSON, the rings are cut into arcs at junctions,
+/// the points that are shared by rings but have different neighbours in each. Each distinct arc
+/// is simplified once, whichever direction it is used in, and the result is used by every ring
+/// that shares it, so that neighbouring rings stay watertight. Rings without junctions are
+/// simplified as closed rings, once for any identical rings. The `closed` option is ignored.
+/// Every ring of at least three distinct points keeps at least three, retaining further points
+/// of its shared arcs where needed.
+///
+/// A repeated first point is retained along with the first point, which may not be retained if
+/// it is in the middle of an arc.
+///
+/// Panics if the offsets are invalid; see [`try_rdp_shared`] for a version that validates its
+/// inputs.
+///
+/// [`rdp_batch`]: crate::rdp_batch
+pub fn rdp_shared<F: CurveFloat>(points: ArrayView2<'_, F>, offsets: &[usize], epsilon: F, options: &RdpOptions) -> Array1<bool> {
+    let mut mask = Array1::from_elem((points.len_of(Axis(0)),), false);
+
+    // Identify each point by its coordinates, so that shared points can be matched.
+    let mut ids = HashMap::new();
+    let point_ids: Vec<usize> = points.outer_iter()
+        .map(|point| {
+            let key: Vec<u64> = point.iter().map(|&c| (c.to_f64().unwrap() + 0.0).to_bits()).collect();
+            let next = ids.len();
+            *ids.entry(key).or_insert(next)
+        })
+        .collect();
+
+    // The points of each ring, without any repeated first point.
+    let mut repeated = Vec::new();
+    let rings: Vec<Vec<usize>> = offsets.windows(2)
+        .filter(|part| part[1] > part[0])
+        .map(|part| {
+            let (start, mut end) = (part[0], part[1]);
+            if end - start > 1 && point_ids[start] == point_ids[end - 1] {
+                end -= 1;
+                repeated.push((start, end));
+            }
+            (start..end).collect()
+        })
+        .collect();
+
+    // Junctions are points that appear with different neighbours in different places.
+    let mut neighbours = HashMap::new();
+    let mut junctions = vec![false; ids.len()];
+    for ring in rings.iter() {
+        let n = ring.len();
+        for k in 0..n {
+            let (previous, next) = (point_ids[ring[(k + n - 1) % n]], point_ids[ring[(k + 1) % n]]);
+            let pair = (previous.min(next), previous.max(next));
+            let id = point_ids[ring[k]];
+            if *neighbours.entry(id).or_insert(pair) != pair {
+                junctions[id] = true;
+            }
+        }
+    }
+
+    let arc_options = RdpOptions { closed: false, ..options.clone() };
+    let ring_options = RdpOptions { closed: true, ..options.clone() };
+    let mut arcs = HashMap::new();
+    let mut closed_rings = HashMap::new();
+    let mut ring_arcs = Vec::new();
+    for ring in rings.iter() {
+        let n = ring.len();
+        let cuts: Vec<usize> = (0..n).filter(|&k| junctions[point_ids[ring[k]]]).collect();
+        if n < 3 {
+            for &i in ring.iter() {
+                mask[i] = true;
+            }
+        } else if cuts.is_empty() {
+            // Start identical rings at the same point and go in the same direction.
+            let first = (0..n).min_by_key(|&k| point_ids[ring[k]]).unwrap();
+            let forwards = point_ids[ring[(first + 1) % n]] <= point_ids[ring[(first + n - 1) % n]];
+            let indices: Vec<usize> = (0..n)
+                .map(|k| if forwards { ring[(first + k) % n] } else { ring[(first + n - k) % n] })
+                .collect();
+            let key: Vec<usize> = indices.iter().map(|&i| point_ids[i]).collect();
+            let ring_mask = closed_rings.entry(key)
+                .or_insert_with(|| rdp_with_options(points.select(Axis(0), &indices).view(), epsilon, &ring_options));
+            for (&i, &keep) in indices.iter().zip(ring_mask.iter()) {
+                mask[i] |= keep;
+            }
+        } else {
+            let mut ring_arc = Vec::with_capacity(cuts.len());
+            for (c, &cut) in cuts.iter().enumerate() {
+                let next_cut = if c + 1 < cuts.len() { cuts[c + 1] } else { cuts[0] + n };
+                let mut indices: Vec<usize> = (cut..=next_cut).map(|k| ring[k % n]).collect();
+
+                // Use the same direction for an arc wherever it is shared.
+                let mut key: Vec<usize> = indices.iter().map(|&i| point_ids[i]).collect();
+                let reversed: Vec<usize> = key.iter().rev().copied().collect();
+                if reversed < key {
+                    key = reversed;
+                    indices.reverse();
+                }
+                arcs.entry(key.clone())
+                    .or_insert_with(|| rdp_with_options(points.select(Axis(0), &indices).view(), epsilon, &arc_options));
+                ring_arc.push((key, indices));
+            }
+            ring_arcs.push(ring_arc);
+        }
+    }
+
+    // Arcs simplified on their own can leave a ring with only its junctions, so retain the
+    // furthest point of its longest arc until it has three distinct points. The point is retained
+    // in the shared arc, so that every ring using it stays consistent.
+    let weights = weights(options);
+    for ring_arc in ring_arcs.iter() {
+        loop {
+            let retained: HashSet<usize> = ring_arc.iter()
+                .flat_map(|(key, _)| key.iter().zip(arcs[key].iter()).filter(|(_, &keep)| keep).map(|(&id, _)| id))
+                .collect();
+            let longest = ring_arc.iter()
+                .filter(|(key, _)| arcs[key].iter().any(|&keep| !keep))
+                .max_by_key(|(key, _)| key.len());
+            let (key, indices) = match longest {
+                Some(arc) if retained.len() < 3 => arc,
+                _ => break,
+            };
+
+            let arc = points.select(Axis(0), indices);
+            let buffer = LineStartPointBuffer::from_points(arc.view(), weights.as_ref().map(|w| w.view()));
+            let distances_2 = line_point_distances_2(arc.row(0), arc.row(indices.len() - 1), &buffer, options.metric);
+            let arc_mask = arcs.get_mut(key).unwrap();
+            let furthest = (0..indices.len())
+                .filter(|&k| !arc_mask[k])
+                .fold(None, |furthest: Option<usize>, k| match furthest {
+                    Some(f) if distances_2[f] >= distances_2[k] => Some(f),
+                    _ => Some(k),
+                });
+            arc_mask[furthest.unwrap()] = true;
+        }
+    }
+
+    for (key, indices) in ring_arcs.iter().flatten() {
+        for (&i, &keep) in indices.iter().zip(arcs[key].iter()) {
+            mask[i] |= keep;
+        }
+    }
+
+    for (first, last) in repeated {
+        mask[last] = mask[first];
+    }
+
+    mask
+}
+
+
+/// Simplifies a collection of rings using the Ramer-Douglas-Peucker algorithm, keeping shared
+/// boundaries consistent, after validating the inputs. See [`rdp_shared`].
+pub fn try_rdp_shared<F: CurveFloat>(points: ArrayView2<'_, F>, offsets: &[usize], epsilon: F, options: &RdpOptions) -> Result<Array1<bool>, CurvedError> {
+    check_offsets(offsets, points.len_of(Axis(0)))?;
+    if points.len_of(Axis(0)) > 0 {
+        check_points(points)?;
+    }
+    check_options(points, epsilon, options)?;
+
+    Ok(rdp_shared(points, offsets, epsilon, options))
+}
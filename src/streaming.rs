@@ -0,0 +1,115 @@
+use std::collections::VecDeque;
+
+use ndarray::{Array1, ArrayView1, ArrayView2};
+
+use crate::{line_point_distances_2, CurveFloat, CurvedError, DistanceMetric, LineStartPointBuffer};
+
+
+/// The default maximum number of points held by a [`StreamingSimplifier`].
+pub const DEFAULT_MAX_WINDOW: usize = 1024;
+
+
+/// Simplifies a curve whose points arrive one at a time, with the same tolerance as [`rdp`].
+///
+/// The points since the last retained point are held in a window. Each new point is accepted
+/// into the window if every point in it stays within `epsilon` of the line from the last
+/// retained point to the new one. Otherwise, or if the window is full, the previous point is
+/// retained and starts a new window. At most `max_window` points are held, and each point takes
+/// O(`max_window`) time.
+///
+/// Retained points, with their index in the stream, are yielded by iterating over the
+/// simplifier as soon as they are final.
+///
+/// [`rdp`]: crate::rdp
+#[derive(Debug, Clone)]
+pub struct StreamingSimplifier<F> {
+    epsilon_2: F,
+    max_window: usize,
+    dims: usize,
+    window: Vec<F>,
+    len: usize,
+    retained: VecDeque<(usize, Array1<F>)>,
+}
+
+
+impl<F: CurveFloat> StreamingSimplifier<F> {
+    /// Creates a simplifier holding at most [`DEFAULT_MAX_WINDOW`] points.
+    pub fn new(epsilon: F) -> StreamingSimplifier<F> {
+        StreamingSimplifier::with_max_window(epsilon, DEFAULT_MAX_WINDOW)
+    }
+
+    /// Creates a simplifier holding at most `max_window` points, which must be at least two.
+    pub fn with_max_window(epsilon: F, max_window: usize) -> StreamingSimplifier<F> {
+        StreamingSimplifier {
+            epsilon_2: epsilon.powi(2),
+            max_window: max_window.max(2),
+            dims: 0,
+            window: Vec::new(),
+            len: 0,
+            retained: VecDeque::new(),
+        }
+    }
+
+    /// Adds the next point of the curve.
+    ///
+    /// Every point of a curve must have the same number of coordinates, all of them finite.
+    pub fn push(&mut self, point: ArrayView1<'_, F>) -> Result<(), CurvedError> {
+        if self.window.is_empty() {
+            if point.is_empty() {
+                return Err(CurvedError::ZeroDimensional);
+            }
+            self.dims = point.len();
+        } else if point.len() != self.dims {
+            return Err(CurvedError::DimensionMismatch(self.dims));
+        }
+        if !point.iter().all(|c| c.is_finite()) {
+            return Err(CurvedError::NonFiniteCoordinate(self.len));
+        }
+        let index = self.len;
+        self.len += 1;
+
+        // The first point of a curve is always retained.
+        if self.window.is_empty() {
+            self.window.extend(point.iter());
+            self.retained.push_back((index, point.to_owned()));
+            return Ok(());
+        }
+
+        let window_len = self.window.len() / self.dims;
+        if window_len >= self.max_window || !self.covers(point) {
+            // The previous point was the last that could be reached from the window start, so
+            // it is retained and starts the next window.
+            let previous = self.window.split_off(self.window.len() - self.dims);
+            self.retained.push_back((index - 1, Array1::from(previous.clone())));
+            self.window = previous;
+        }
+        self.window.extend(point.iter());
+        Ok(())
+    }
+
+    /// Ends the curve, retaining its last point. The next point pushed starts a new curve.
+    pub fn flush(&mut self) {
+        if self.window.len() > self.dims {
+            let last = self.window.split_off(self.window.len() - self.dims);
+            self.retained.push_back((self.len - 1, Array1::from(last)));
+        }
+        self.window.clear();
+    }
+
+    /// Whether every point in the window is within epsilon of the line to the given point.
+    fn covers(&self, point: ArrayView1<'_, F>) -> bool {
+        let window = ArrayView2::from_shape((self.window.len() / self.dims, self.dims), &self.window).unwrap();
+        let buffer = LineStartPointBuffer::from_points(window, None);
+        let distances_2 = line_point_distances_2(window.row(0), point, &buffer, DistanceMetric::Line);
+        distances_2.iter().all(|&d_2| d_2 <= self.epsilon_2)
+    }
+}
+
+
+impl<F: CurveFloat> Iterator for StreamingSimplifier<F> {
+    type Item = (usize, Array1<F>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.retained.pop_front()
+    }
+}
@@ -0,0 +1,190 @@
+use ndarray::{Axis, Array1, ArrayView1, ArrayView2, Zip};
+
+use crate::{check_options, check_points, CurveFloat, CurvedError, RdpOptions};
+
+
+/// Simplifies a curve using the Reumann-Witkam algorithm.
+///
+/// A strip of half-width `tolerance` is laid along the line through the last retained point and
+/// the point after it. Points are discarded while they stay within the strip, and the last point
+/// inside it is retained and starts the next strip. Runs in a single pass in O(n) time. The
+/// endpoints are always retained. Works for N dimensional points.
+pub fn reumann_witkam<F: CurveFloat>(points: ArrayView2<'_, F>, tolerance: F) -> Array1<bool> {
+    simplify_strip(points, tolerance, StripKind::ReumannWitkam)
+}
+
+
+/// Simplifies a curve using the Opheim algorithm.
+///
+/// Like [`reumann_witkam`], except that the strip is a ray starting at the last retained point
+/// and pointing at the first point further than `tolerance` from it, and that it ends
+/// `max_distance` from the last retained point. Runs in a single pass in O(n) time. The endpoints
+/// are always retained. Works for N dimensional points.
+///
+/// `max_distance` must be non-negative and not NaN, otherwise the strip ends immediately or
+/// never, and the result is meaningless.
+pub fn opheim<F: CurveFloat>(points: ArrayView2<'_, F>, tolerance: F, max_distance: F) -> Array1<bool> {
+    simplify_strip(points, tolerance, StripKind::Opheim { max_distance: max_distance.to_f64().unwrap() })
+}
+
+
+/// Simplifies a curve using the Opheim algorithm, after validating the inputs.
+pub fn try_opheim<F: CurveFloat>(points: ArrayView2<'_, F>, tolerance: F, max_distance: F) -> Result<Array1<bool>, CurvedError> {
+    check_points(points)?;
+    check_options(points, tolerance, &RdpOptions::default())?;
+    check_max_distance(max_distance.to_f64().unwrap())?;
+
+    Ok(opheim(points, tolerance, max_distance))
+}
+
+
+/// Checks that a maximum distance for [`opheim`] is non-negative and not NaN.
+pub(crate) fn check_max_distance(max_distance: f64) -> Result<(), CurvedError> {
+    if max_distance.is_nan() || max_distance < 0.0 {
+        return Err(CurvedError::InvalidMaxDistance(max_distance));
+    }
+    Ok(())
+}
+
+
+/// The shape of the strip within which points are discarded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum StripKind {
+    ReumannWitkam,
+    Opheim { max_distance: f64 },
+}
+
+
+pub(crate) fn simplify_strip<F: CurveFloat>(points: ArrayView2<'_, F>, tolerance: F, kind: StripKind) -> Array1<bool> {
+    let mut mask = Array1::from_elem((points.len_of(Axis(0)),), false);
+    let mut strip = StripState::new(tolerance, kind);
+    for point in points.outer_iter() {
+        if let Some(i) = strip.push(point) {
+            mask[i] = true;
+        }
+    }
+    if let Some(i) = strip.finish() {
+        mask[i] = true;
+    }
+    mask
+}
+
+
+/// The state of a strip simplification, which takes the points one at a time and reports each
+/// retained point as soon as it is known. Only the key, direction and previous points are kept.
+pub(crate) struct StripState<F> {
+    tolerance_2: F,
+    kind: StripKind,
+    max_distance_2: F,
+    key: Option<Array1<F>>,
+    direction: Option<Array1<F>>,
+    previous: Option<Array1<F>>,
+    len: usize,
+}
+
+
+impl<F: CurveFloat> StripState<F> {
+    pub(crate) fn new(tolerance: F, kind: StripKind) -> StripState<F> {
+        let max_distance_2 = match kind {
+            StripKind::ReumannWitkam => F::infinity(),
+            StripKind::Opheim { max_distance } => F::from(max_distance).unwrap().powi(2),
+        };
+        StripState {
+            tolerance_2: tolerance.powi(2),
+            kind,
+            max_distance_2,
+            key: None,
+            direction: None,
+            previous: None,
+            len: 0,
+        }
+    }
+
+    /// Adds the next point, returning the index of a point that must be retained, if any.
+    pub(crate) fn push(&mut self, point: ArrayView1<'_, F>) -> Option<usize> {
+        let index = self.len;
+        self.len += 1;
+
+        let key = match &self.key {
+            Some(key) => key,
+            None => {
+                // The first point is always retained.
+                self.key = Some(point.to_owned());
+                return Some(index);
+            },
+        };
+
+        let retained = if self.direction.is_none() {
+            // Until a direction has been found, points close to the key are discarded.
+            self.set_direction(point);
+            None
+        } else if self.outside(key.view(), point) {
+            // The previous point was the last inside the strip, so it starts the next one.
+            self.key = self.previous.take();
+            self.direction = None;
+            self.set_direction(point);
+            Some(index - 1)
+        } else {
+            None
+        };
+
+        self.previous = Some(point.to_owned());
+        retained
+    }
+
+    /// Finishes the curve, returning the index of the last point if it wasn't already retained.
+    pub(crate) fn finish(&mut self) -> Option<usize> {
+        let retained = match self.previous {
+            Some(_) => Some(self.len - 1),
+            None => None,
+        };
+        self.key = None;
+        self.direction = None;
+        self.previous = None;
+        self.len = 0;
+        retained
+    }
+
+    fn set_direction(&mut self, point: ArrayView1<'_, F>) {
+        let key = self.key.as_ref().unwrap();
+        let v = &point - key;
+        let v_2 = v.dot(&v);
+
+        // Reumann-Witkam takes the direction from the next distinct point, whereas Opheim takes
+        // it from the first point outside the tolerance.
+        let radius_2 = match self.kind {
+            StripKind::ReumannWitkam => F::zero(),
+            StripKind::Opheim { .. } => self.tolerance_2,
+        };
+        if v_2 > radius_2 {
+            self.direction = Some(v / v_2.sqrt());
+        }
+    }
+
+    fn outside(&self, key: ArrayView1<'_, F>, point: ArrayView1<'_, F>) -> bool {
+        let direction = self.direction.as_ref().unwrap();
+        let mut v_2 = F::zero();
+        let mut t = F::zero();
+        Zip::from(&point).and(&key).and(direction).for_each(|&p, &k, &d| {
+            v_2 = v_2 + (p - k) * (p - k);
+            t = t + (p - k) * d;
+        });
+        if v_2 > self.max_distance_2 {
+            return true;
+        }
+
+        // Points behind the start of an Opheim ray are measured from the key itself.
+        if let StripKind::Opheim { .. } = self.kind {
+            if t < F::zero() {
+                return v_2 > self.tolerance_2;
+            }
+        }
+
+        let mut d_2 = F::zero();
+        Zip::from(&point).and(&key).and(direction).for_each(|&p, &k, &d| {
+            let e = p - k - t * d;
+            d_2 = d_2 + e * e;
+        });
+        d_2 > self.tolerance_2
+    }
+}
@@ -0,0 +1,102 @@
+use ndarray::{Array1, ArrayView2};
+
+use crate::CurveFloat;
+
+
+/// Retains further points until no two segments of the simplified curve cross each other, in the
+/// plane of the first two coordinates.
+///
+/// Each segment that crosses another is split at the point given by `split`, which must be
+/// strictly between its endpoints, until no shortcut crosses. Segments of the original curve
+/// can't be split, so crossings in the original curve are left alone.
+pub(crate) fn untangle<F: CurveFloat>(
+    points: ArrayView2<'_, F>,
+    mask: &mut Array1<bool>,
+    mut split: impl FnMut(usize, usize) -> usize
+) {
+    let plane: Vec<[f64; 2]> = points.outer_iter()
+        .map(|point| [point[0].to_f64().unwrap(), point[1].to_f64().unwrap()])
+        .collect();
+
+    loop {
+        let retained: Vec<usize> = mask.iter().enumerate().filter(|(_, &keep)| keep).map(|(i, _)| i).collect();
+        let segments: Vec<(usize, usize)> = retained.windows(2).map(|pair| (pair[0], pair[1])).collect();
+
+        let mut untangled = true;
+        for (&(start, end), crosses) in segments.iter().zip(crossing_segments(&plane, &segments)) {
+            if crosses && end > start + 1 {
+                mask[split(start, end)] = true;
+                untangled = false;
+            }
+        }
+        if untangled {
+            return;
+        }
+    }
+}
+
+
+/// Which of the segments cross any other, other than by sharing an endpoint.
+fn crossing_segments(plane: &[[f64; 2]], segments: &[(usize, usize)]) -> Vec<bool> {
+    // Sweep along x, so that only segments with overlapping extents are compared.
+    let bounds: Vec<(f64, f64)> = segments.iter()
+        .map(|&(a, b)| (plane[a][0].min(plane[b][0]), plane[a][0].max(plane[b][0])))
+        .collect();
+    let mut order: Vec<usize> = (0..segments.len()).collect();
+    order.sort_by(|&i, &j| bounds[i].0.total_cmp(&bounds[j].0));
+
+    let mut crossing = vec![false; segments.len()];
+    for (n, &i) in order.iter().enumerate() {
+        for &j in order[n + 1..].iter() {
+            if bounds[j].0 > bounds[i].1 {
+                break;
+            }
+            let (p, q) = (segments[i], segments[j]);
+            if cross(plane[p.0], plane[p.1], plane[q.0], plane[q.1]) {
+                crossing[i] = true;
+                crossing[j] = true;
+            }
+        }
+    }
+    crossing
+}
+
+
+/// Whether the segments ab and cd intersect anywhere other than at a shared endpoint.
+fn cross(a: [f64; 2], b: [f64; 2], c: [f64; 2], d: [f64; 2]) -> bool {
+    // Segments that share an endpoint only cross if they double back along each other.
+    let shared = if a == c || a == d {
+        Some((a, b, if a == c { d } else { c }))
+    } else if b == c || b == d {
+        Some((b, a, if b == c { d } else { c }))
+    } else {
+        None
+    };
+    if let Some((shared, p, q)) = shared {
+        let (u, v) = ([p[0] - shared[0], p[1] - shared[1]], [q[0] - shared[0], q[1] - shared[1]]);
+        return u[0] * v[1] - u[1] * v[0] == 0.0 && u[0] * v[0] + u[1] * v[1] > 0.0;
+    }
+
+    let (o1, o2) = (orientation(a, b, c), orientation(a, b, d));
+    let (o3, o4) = (orientation(c, d, a), orientation(c, d, b));
+    if o1 * o2 < 0.0 && o3 * o4 < 0.0 {
+        return true;
+    }
+
+    // Otherwise they can only touch, where an endpoint is on the other segment.
+    (o1 == 0.0 && on_segment(a, b, c))
+        || (o2 == 0.0 && on_segment(a, b, d))
+        || (o3 == 0.0 && on_segment(c, d, a))
+        || (o4 == 0.0 && on_segment(c, d, b))
+}
+
+
+fn orientation(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+
+/// Whether c, collinear with a and b, is between them.
+fn on_segment(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> bool {
+    a[0].min(b[0]) <= c[0] && c[0] <= a[0].max(b[0]) && a[1].min(b[1]) <= c[1] && c[1] <= a[1].max(b[1])
+}
@@ -0,0 +1,28 @@
+use ndarray::{Array1, ArrayView1};
+
+use crate::{CurveFloat, LineStartPointBuffer};
+
+
+/// Calculates the squared synchronized Euclidean distances from each point to the position
+/// interpolated at the same time between the start (A) and end (B). The column at index `time`
+/// holds the times, and the remaining columns the positions.
+pub(crate) fn synchronized_distances_2<F: CurveFloat>(
+    start: ArrayView1<'_, F>,
+    end: ArrayView1<'_, F>,
+    buffer: &LineStartPointBuffer<'_, F>,
+    time: usize
+) -> Array1<F> {
+    let ab = crate::line_vector(start, end, buffer);
+
+    // If the start and end are at the same time there is nothing to interpolate, so use the
+    // distance from the start.
+    let ab_time = ab[time];
+    buffer.vectors.outer_iter()
+        .map(|ac| {
+            let ratio = if ab_time.is_zero() { F::zero() } else { ac[time] / ab_time };
+            ac.iter().zip(ab.iter()).enumerate()
+                .filter(|&(i, _)| i != time)
+                .fold(F::zero(), |d_2, (_, (&ac, &ab))| d_2 + (ac - ratio * ab).powi(2))
+        })
+        .collect()
+}
@@ -0,0 +1,107 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use ndarray::{Axis, Array1, ArrayView1, ArrayView2};
+
+use crate::CurveFloat;
+
+
+/// Simplifies a curve using the Visvalingam-Whyatt algorithm.
+///
+/// Points are repeatedly removed in order of the smallest effective area (the area of the
+/// triangle formed with their neighbours) until every remaining point has an effective area
+/// of at least `min_area`. The endpoints are always retained. Works for N dimensional points.
+pub fn visvalingam<F: CurveFloat>(points: ArrayView2<'_, F>, min_area: F) -> Array1<bool> {
+    let n = points.len_of(Axis(0));
+    let min_area = min_area.to_f64().unwrap();
+    let mut mask = Array1::from_elem((n,), true);
+    if n < 3 {
+        return mask;
+    }
+
+    // Doubly linked list of the points that are still retained.
+    let mut prev: Vec<usize> = (0..n).map(|i| i.wrapping_sub(1)).collect();
+    let mut next: Vec<usize> = (1..=n).collect();
+
+    // Min-heap of candidate removals. Entries are invalidated lazily by comparing against the
+    // current area of the point.
+    let mut areas = vec![f64::INFINITY; n];
+    let mut heap = BinaryHeap::with_capacity(n);
+    for (i, area) in areas.iter_mut().enumerate().take(n - 1).skip(1) {
+        *area = triangle_area(points.row(i - 1), points.row(i), points.row(i + 1));
+        heap.push(Candidate { area: *area, index: i });
+    }
+
+    // Effective areas are never allowed to drop below the area of the last point removed, so
+    // that the removal order is monotonic.
+    let mut last_area = f64::NEG_INFINITY;
+    while let Some(Candidate { area, index }) = heap.pop() {
+        if !mask[index] || area != areas[index] {
+            continue;
+        }
+        if area >= min_area {
+            break;
+        }
+        last_area = last_area.max(area);
+        mask[index] = false;
+
+        // Unlink the point and recalculate the areas of its neighbours.
+        let (p, q) = (prev[index], next[index]);
+        next[p] = q;
+        prev[q] = p;
+        for &j in [p, q].iter() {
+            if j == 0 || j == n - 1 {
+                continue;
+            }
+            let a = triangle_area(points.row(prev[j]), points.row(j), points.row(next[j]))
+                .max(last_area);
+            areas[j] = a;
+            heap.push(Candidate { area: a, index: j });
+        }
+    }
+
+    mask
+}
+
+
+/// The area of the triangle abc. Areas are compared as `f64` whatever the type of the points.
+fn triangle_area<F: CurveFloat>(a: ArrayView1<'_, F>, b: ArrayView1<'_, F>, c: ArrayView1<'_, F>) -> f64 {
+    // Use the Gram determinant so that the area is defined in any number of dimensions.
+    let ab = &b - &a;
+    let ac = &c - &a;
+    let ab_ac = ab.dot(&ac);
+    let gram = ab.dot(&ab) * ac.dot(&ac) - ab_ac * ab_ac;
+    0.5 * gram.max(F::zero()).sqrt().to_f64().unwrap()
+}
+
+
+struct Candidate {
+    area: f64,
+    index: usize,
+}
+
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+
+impl Eq for Candidate {}
+
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, so that the standard max-heap pops the smallest area (and lowest index on
+        // ties) first.
+        other.area.total_cmp(&self.area).then_with(|| other.index.cmp(&self.index))
+    }
+}
@@ -0,0 +1,36 @@
+use ndarray::{array, s, Array2};
+
+#[test]
+fn rdp_batch_matches_parts() {
+    let points = include!("../fixtures/norway_main.rs");
+    let offsets = [0, 1, 1, 3000, 5000, points.nrows()];
+    let options = curved::RdpOptions::default();
+    let mask = curved::rdp_batch(points.view(), &offsets, 0.001, &options);
+    for part in offsets.windows(2).filter(|part| part[1] > part[0]) {
+        assert_eq!(
+            mask.slice(s![part[0]..part[1]]),
+            curved::rdp(points.slice(s![part[0]..part[1], ..]), 0.001).view()
+        );
+    }
+
+    // Without the rayon feature, the parallel option is ignored.
+    let options = curved::RdpOptions { parallel: true, ..Default::default() };
+    assert_eq!(curved::rdp_batch(points.view(), &offsets, 0.001, &options), mask);
+}
+
+#[test]
+fn try_rdp_batch_errors() {
+    let points = array![[0.0, 0.0], [1.0, 1.0], [2.0, 0.0], [3.0, 0.0]];
+    let options = curved::RdpOptions::default();
+    assert_eq!(
+        curved::try_rdp_batch(points.view(), &[0, 2, 4], 0.5, &options),
+        Ok(array![true, true, true, true])
+    );
+    for offsets in [&[][..], &[1, 4], &[0, 3], &[0, 3, 2, 4]].iter() {
+        assert_eq!(
+            curved::try_rdp_batch(points.view(), offsets, 0.5, &options),
+            Err(curved::CurvedError::InvalidOffsets)
+        );
+    }
+    assert_eq!(curved::try_rdp_batch(Array2::<f64>::zeros((0, 2)).view(), &[0], 0.5, &options), Ok(array![]));
+}
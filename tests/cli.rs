@@ -0,0 +1,69 @@
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn curved(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_curved"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    // The command may exit without reading its input.
+    let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn cli_text() {
+    let csv = "x,y,label\n0,0,a\n1,0.1,b\n2,-0.1,c\n3,5,d\n4,6,e\n5,7,f\n";
+    let output = curved(&["--epsilon", "0.5", "--dims", "2"], csv);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "x,y,label\n0,0,a\n2,-0.1,c\n3,5,d\n5,7,f\n");
+
+    // A comment with commas doesn't make the input CSV.
+    let output = curved(&["--epsilon", "0.5", "--output", "indices"], "# x, y\n0 0\n1 0.1\n2 -0.1\n");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "0\n2\n");
+
+    let text = "0 0\n1 0.1\n2 -0.1\n3 5\n4 6\n5 7\n";
+    let output = curved(&["--target-points", "3", "--output", "indices"], text);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "0\n2\n5\n");
+
+    let output = curved(&["--algorithm", "visvalingam", "--epsilon", "0.5", "--output", "indices"], text);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "0\n2\n3\n5\n");
+}
+
+#[test]
+fn cli_geojson() {
+    let feature = r#"{"type":"Feature","id":7,"properties":{"name":"a"},"geometry":{"type":"LineString","coordinates":[[0,0],[1,0.1],[2,-0.1],[3,5],[4,6],[5,7]]}}"#;
+    let output = curved(&["--epsilon", "0.5"], feature);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "{\"type\":\"Feature\",\"id\":7,\"properties\":{\"name\":\"a\"},\"geometry\":{\"type\":\"LineString\",\"coordinates\":[[0,0],[2,-0.1],[3,5],[5,7]]}}\n"
+    );
+
+    let output = curved(&["--epsilon", "0.5"], r#"{"type":"Point","coordinates":[0,0]}"#);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn cli_errors() {
+    // Invalid options exit with 2, and invalid input with 1.
+    assert_eq!(curved(&[], "0 0\n1 1\n").status.code(), Some(2));
+    assert_eq!(curved(&["--epsilon", "-1"], "0 0\n1 1\n").status.code(), Some(2));
+    assert_eq!(curved(&["--algorithm", "lang", "--epsilon", "1"], "0 0\n1 1\n").status.code(), Some(2));
+    let opheim = ["--algorithm", "opheim", "--epsilon", "1", "--max-distance"];
+    assert_eq!(curved(&[&opheim[..], &["-1"]].concat(), "0 0\n1 1\n").status.code(), Some(2));
+    assert_eq!(curved(&[&opheim[..], &["nan"]].concat(), "0 0\n1 1\n").status.code(), Some(2));
+    assert_eq!(curved(&["--algorithm", "imai-iri", "--target-points", "2"], "0 0\n1 1\n").status.code(), Some(2));
+    assert_eq!(curved(&["--epsilon", "1"], "").status.code(), Some(1));
+    assert_eq!(curved(&["--epsilon", "1"], "0 0\n1 x\n").status.code(), Some(1));
+    assert_eq!(curved(&["--epsilon", "1"], "0 0\n1 1 1\n").status.code(), Some(1));
+    assert_eq!(curved(&["--epsilon", "1", "/nonexistent"], "").status.code(), Some(1));
+}
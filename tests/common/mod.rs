@@ -0,0 +1,21 @@
+use ndarray::ArrayView2;
+
+/// Asserts that the endpoints are retained, that every discarded point is within epsilon of the
+/// line between the retained points either side of it, as with RDP, and that fewer than twice as
+/// many points are retained as by RDP.
+pub fn assert_within_tolerance(points: ArrayView2<'_, f64>, retained: &[usize], epsilon: f64) {
+    assert_eq!(retained[0], 0);
+    assert_eq!(*retained.last().unwrap(), points.nrows() - 1);
+    for pair in retained.windows(2) {
+        let (a, b) = (points.row(pair[0]), points.row(pair[1]));
+        let ab = &b - &a;
+        for i in pair[0] + 1..pair[1] {
+            let ap = &points.row(i) - &a;
+            let cross: f64 = ab[0] * ap[1] - ab[1] * ap[0];
+            assert!(cross.abs() / ab.dot(&ab).sqrt() <= epsilon * (1.0 + 1e-9));
+        }
+    }
+
+    let rdp = curved::rdp(points, epsilon).iter().filter(|&&keep| keep).count();
+    assert!(retained.len() < rdp * 2);
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "geo")]
+
+use curved::{CurvedSimplify, RdpOptions};
+use geo_types::{line_string, polygon, Coord, LineString, MultiLineString, MultiPolygon};
+use ndarray::array;
+
+fn norway() -> LineString<f64> {
+    let points = include!("../fixtures/norway_main.rs");
+    points.outer_iter().map(|point| Coord { x: point[0], y: point[1] }).collect()
+}
+
+#[test]
+fn curved_simplify_line_string() {
+    let points = include!("../fixtures/norway_main.rs");
+    let (simplified, mask) = norway().curved_simplify(0.0005, &RdpOptions::default());
+    assert_eq!(mask, curved::rdp(points.view(), 0.0005));
+    assert_eq!(simplified.0.len(), mask.iter().filter(|&&keep| keep).count());
+
+    let (empty, mask) = LineString::<f64>::new(vec![]).curved_simplify(1.0, &RdpOptions::default());
+    assert!(empty.0.is_empty() && mask.is_empty());
+}
+
+#[test]
+fn curved_simplify_polygon() {
+    let polygon = polygon!(
+        exterior: [(x: 0.0, y: 0.0), (x: 5.0, y: 0.1), (x: 10.0, y: 0.0), (x: 10.0, y: 10.0), (x: 0.0, y: 10.0)],
+        interiors: [[(x: 1.0, y: 1.0), (x: 2.0, y: 1.0), (x: 3.0, y: 1.0)]],
+    );
+    let (simplified, masks) = polygon.curved_simplify(1.0, &RdpOptions::default());
+    assert_eq!(simplified.exterior().0.len(), 5);
+    assert_eq!(masks[0].to_vec(), vec![true, false, true, true, true, true]);
+
+    // The collinear hole still keeps four coordinates.
+    assert_eq!(simplified.interiors()[0].0.len(), 4);
+    assert_eq!(masks.len(), 2);
+
+    let multi_polygon = MultiPolygon::new(vec![polygon.clone(), polygon]);
+    let (simplified_multi, multi_masks) = multi_polygon.curved_simplify(1.0, &RdpOptions::default());
+    assert_eq!(simplified_multi.0, vec![simplified.clone(), simplified]);
+    assert_eq!(multi_masks, vec![masks.clone(), masks]);
+}
+
+#[test]
+fn curved_simplify_multi_line_string() {
+    let lines = MultiLineString::new(vec![norway(), line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 0.01), (x: 2.0, y: 0.0)]]);
+    let (simplified, masks) = lines.curved_simplify(0.1, &RdpOptions::default());
+    assert_eq!(masks[1].to_vec(), vec![true, false, true]);
+    assert_eq!(simplified.0[1], line_string![(x: 0.0, y: 0.0), (x: 2.0, y: 0.0)]);
+    assert_eq!(simplified.0[0], norway().curved_simplify(0.1, &RdpOptions::default()).0);
+}
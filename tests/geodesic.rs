@@ -0,0 +1,42 @@
+use ndarray::array;
+
+use curved::{DistanceMetric, Ellipsoid, RdpOptions};
+
+#[test]
+fn rdp_spherical_tolerance_in_metres() {
+    // The middle point is 1km east of a meridian at the equator.
+    let degrees = 1000.0 / 6_371_008.8f64.to_radians();
+    let points = array![[0.0, -0.5], [degrees, 0.0], [0.0, 0.5]];
+    let options = RdpOptions { metric: DistanceMetric::Geographic(Ellipsoid::Sphere), ..Default::default() };
+    assert_eq!(curved::rdp_with_options(points.view(), 990.0, &options), array![true, true, true]);
+    assert_eq!(curved::rdp_with_options(points.view(), 1010.0, &options), array![true, false, true]);
+
+    // At 60 degrees north the same offset in longitude is only half the distance.
+    let points = array![[0.0, 59.5], [degrees, 60.0], [0.0, 60.5]];
+    assert_eq!(curved::rdp_with_options(points.view(), 490.0, &options), array![true, true, true]);
+    assert_eq!(curved::rdp_with_options(points.view(), 510.0, &options), array![true, false, true]);
+}
+
+#[test]
+fn rdp_wgs84_norway() {
+    // Around 50m on the ground is roughly the 0.0005 degree tolerance at these latitudes, and
+    // the ellipsoid should only differ slightly from the sphere.
+    let points = include!("../fixtures/norway_main.rs");
+    let count = |ellipsoid| {
+        let options = RdpOptions { metric: DistanceMetric::Geographic(ellipsoid), ..Default::default() };
+        curved::rdp_with_options(points.view(), 50.0, &options).iter().filter(|&&keep| keep).count()
+    };
+    let (sphere, wgs84) = (count(Ellipsoid::Sphere), count(Ellipsoid::Wgs84));
+    assert!(sphere > 1000 && sphere < points.nrows());
+    assert!((sphere as f64 - wgs84 as f64).abs() < 0.02 * sphere as f64);
+}
+
+#[test]
+fn try_rdp_geographic_needs_two_dimensions() {
+    let points = array![[0.0], [1.0], [2.0]];
+    let options = RdpOptions { metric: DistanceMetric::Geographic(Ellipsoid::Sphere), ..Default::default() };
+    assert_eq!(
+        curved::try_rdp_with_options(points.view(), 1.0, &options),
+        Err(curved::CurvedError::TooFewDimensions(2))
+    );
+}
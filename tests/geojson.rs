@@ -0,0 +1,106 @@
+#![cfg(feature = "geojson")]
+
+use curved::{Algorithm, GeoJsonError, RdpOptions};
+use geojson::{FeatureCollection, Value};
+use ndarray::array;
+
+fn norway() -> String {
+    let points = include!("../fixtures/norway_main.rs");
+    let coordinates: Vec<String> = points.outer_iter().map(|point| format!("[{},{},7]", point[0], point[1])).collect();
+    format!(
+        r#"{{"type":"FeatureCollection","features":[
+            {{"type":"Feature","id":"norway","properties":{{"name":"Norway"}},"geometry":{{"type":"LineString","coordinates":[{}]}}}},
+            {{"type":"Feature","id":2,"properties":null,"geometry":{{"type":"Polygon","coordinates":[
+                [[0,0],[5,0.0001],[10,0],[10,10],[0,10],[0,0]],
+                [[1,1],[2,1.0001],[3,1],[1,1]]
+            ]}}}},
+            {{"type":"Feature","properties":{{"kind":"point"}},"geometry":{{"type":"Point","coordinates":[1,2]}}}},
+            {{"type":"Feature","properties":{{"kind":"none"}},"geometry":null}}
+        ]}}"#,
+        coordinates.join(",")
+    )
+}
+
+#[test]
+fn simplify_geojson_rdp() {
+    let points = include!("../fixtures/norway_main.rs");
+    let mask = curved::rdp(points.view(), 0.0005);
+
+    let simplified: FeatureCollection = curved::simplify_geojson(&norway(), 0.0005, &Algorithm::default())
+        .unwrap()
+        .parse()
+        .unwrap();
+    let original: FeatureCollection = norway().parse().unwrap();
+    assert_eq!(simplified.features.len(), 4);
+    for (simplified, original) in simplified.features.iter().zip(original.features.iter()) {
+        assert_eq!(simplified.id, original.id);
+        assert_eq!(simplified.properties, original.properties);
+    }
+
+    // Further coordinates are kept with the positions retained.
+    match &simplified.features[0].geometry.as_ref().unwrap().value {
+        Value::LineString(line) => {
+            assert_eq!(line.len(), mask.iter().filter(|&&keep| keep).count());
+            assert!(line.iter().all(|position| position.len() == 3 && position[2] == 7.0));
+        }
+        value => panic!("unexpected geometry {:?}", value),
+    }
+
+    // The small hole would be reduced to a triangle, so is left alone.
+    match &simplified.features[1].geometry.as_ref().unwrap().value {
+        Value::Polygon(rings) => {
+            assert_eq!(rings[0], vec![vec![0.0, 0.0], vec![10.0, 0.0], vec![10.0, 10.0], vec![0.0, 10.0], vec![0.0, 0.0]]);
+            assert_eq!(rings[1].len(), 4);
+        }
+        value => panic!("unexpected geometry {:?}", value),
+    }
+    assert_eq!(simplified.features[2].geometry, original.features[2].geometry);
+    assert_eq!(simplified.features[3].geometry, None);
+}
+
+#[test]
+fn simplify_feature_collection_algorithms() {
+    let collection: FeatureCollection = norway().parse().unwrap();
+    let points = include!("../fixtures/norway_main.rs");
+    let algorithms = [
+        (Algorithm::Visvalingam, curved::visvalingam(points.view(), 1e-6)),
+        (Algorithm::ImaiIri, curved::imai_iri(points.view(), 1e-6)),
+        (Algorithm::Lang { look_ahead: 8 }, curved::lang(points.view(), 1e-6, 8)),
+        (Algorithm::ReumannWitkam, curved::reumann_witkam(points.view(), 1e-6)),
+        (Algorithm::Opheim { max_distance: 0.1 }, curved::opheim(points.view(), 1e-6, 0.1)),
+    ];
+    for (algorithm, mask) in algorithms.iter() {
+        let simplified = curved::simplify_feature_collection(&collection, 1e-6, algorithm).unwrap();
+        match &simplified.features[0].geometry.as_ref().unwrap().value {
+            Value::LineString(line) => assert_eq!(line.len(), mask.iter().filter(|&&keep| keep).count()),
+            value => panic!("unexpected geometry {:?}", value),
+        }
+    }
+}
+
+#[test]
+fn simplify_geojson_errors() {
+    assert!(matches!(curved::simplify_geojson("{", 1.0, &Algorithm::default()), Err(GeoJsonError::Parse(_))));
+    assert!(matches!(
+        curved::simplify_geojson(r#"{"type":"Point","coordinates":[1,2]}"#, 1.0, &Algorithm::default()),
+        Err(GeoJsonError::Parse(_))
+    ));
+    assert!(matches!(
+        curved::simplify_geojson(&norway(), -1.0, &Algorithm::default()),
+        Err(GeoJsonError::Simplify(curved::CurvedError::InvalidEpsilon(_)))
+    ));
+
+    let weights = RdpOptions { weights: Some(vec![1.0, 1.0, 1.0]), ..Default::default() };
+    assert!(matches!(
+        curved::simplify_geojson(&norway(), 1.0, &Algorithm::Rdp(weights)),
+        Err(GeoJsonError::Simplify(curved::CurvedError::InvalidWeights))
+    ));
+    assert!(matches!(
+        curved::simplify_geojson(&norway(), 1.0, &Algorithm::Opheim { max_distance: -1.0 }),
+        Err(GeoJsonError::Simplify(curved::CurvedError::InvalidMaxDistance(_)))
+    ));
+    assert!(matches!(
+        curved::simplify_geojson(&norway(), 1.0, &Algorithm::Opheim { max_distance: f64::NAN }),
+        Err(GeoJsonError::Simplify(curved::CurvedError::InvalidMaxDistance(_)))
+    ));
+}
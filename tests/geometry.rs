@@ -0,0 +1,57 @@
+use ndarray::{array, s, Array2, Axis};
+
+use curved::{MultiLineString, MultiPolygon, Polygon, RdpOptions};
+
+fn assert_valid_ring(ring: &Array2<f64>) {
+    let len = ring.len_of(Axis(0));
+    assert!(len >= 4);
+    assert_eq!(ring.row(0), ring.row(len - 1));
+}
+
+#[test]
+fn rdp_polygon_keeps_rings_valid() {
+    let exterior = include!("../fixtures/norway_main.rs");
+    let polygon = Polygon {
+        exterior: exterior.clone(),
+        interiors: vec![
+            // A small hole, not closed explicitly, which would otherwise collapse.
+            array![[10.0, 60.0], [10.001, 60.0], [10.0005, 60.001]],
+            // A hole with all of its points on a line.
+            array![[11.0, 61.0], [11.1, 61.0], [11.2, 61.0], [11.0, 61.0]],
+        ],
+    };
+    let simplified = curved::rdp_polygon(&polygon, 1.0, &RdpOptions::default());
+    assert!(simplified.exterior.nrows() < exterior.nrows());
+    assert_valid_ring(&simplified.exterior);
+    for ring in simplified.interiors.iter() {
+        assert_valid_ring(ring);
+    }
+    assert_eq!(simplified.interiors[0].slice(s![..3, ..]), polygon.interiors[0]);
+    assert_eq!(simplified.interiors[1], polygon.interiors[1]);
+
+    let multi_polygon = MultiPolygon { polygons: vec![polygon.clone(), polygon] };
+    let simplified_multi = curved::rdp_multi_polygon(&multi_polygon, 1.0, &RdpOptions::default());
+    assert_eq!(simplified_multi.polygons, vec![simplified.clone(), simplified]);
+}
+
+#[test]
+fn rdp_ring_large_epsilon() {
+    let ring = array![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.5, 1.1], [0.0, 1.0], [0.0, 0.0]];
+    let simplified = curved::rdp_ring(ring.view(), 100.0, &RdpOptions::default());
+    assert_valid_ring(&simplified);
+    assert_eq!(simplified.nrows(), 4);
+    assert_eq!(curved::rdp_ring(Array2::<f64>::zeros((0, 2)).view(), 1.0, &RdpOptions::default()).nrows(), 0);
+}
+
+#[test]
+fn rdp_multi_line_string_matches_rdp() {
+    let points = include!("../fixtures/norway_main.rs");
+    let lines = vec![points.slice(s![..3000, ..]).to_owned(), Array2::zeros((0, 2)), points.slice(s![3000.., ..]).to_owned()];
+    let simplified = curved::rdp_multi_line_string(&MultiLineString { lines: lines.clone() }, 0.01, &RdpOptions::default());
+    assert_eq!(simplified.lines[1].nrows(), 0);
+    for &i in [0, 2].iter() {
+        let mask = curved::rdp(lines[i].view(), 0.01);
+        assert_eq!(simplified.lines[i].nrows(), mask.iter().filter(|&&keep| keep).count());
+        assert_eq!(simplified.lines[i].row(0), lines[i].row(0));
+    }
+}
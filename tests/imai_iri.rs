@@ -0,0 +1,66 @@
+use ndarray::{array, Array2, Axis};
+use proptest::prelude::*;
+
+fn count(mask: &ndarray::Array1<bool>) -> usize {
+    mask.iter().filter(|&&keep| keep).count()
+}
+
+#[test]
+fn imai_iri_fewer_than_rdp() {
+    // RDP splits at the furthest point and has to keep every point, whereas the first two points
+    // are within epsilon of the line to the fourth.
+    let points = array![[3.0, 2.0], [4.0, 3.0], [6.0, 1.0], [1.0, 2.0], [6.0, 6.0], [5.0, 1.0]];
+    assert_eq!(curved::imai_iri(points.view(), 1.0), array![true, false, false, true, true, true]);
+    assert_eq!(count(&curved::rdp(points.view(), 1.0)), 6);
+}
+
+#[test]
+fn imai_iri_norway() {
+    let points = include!("../fixtures/norway_main.rs");
+    for &epsilon in [0.0005, 0.01, 0.1].iter() {
+        let mask = curved::imai_iri(points.view(), epsilon);
+        assert!(count(&mask) <= count(&curved::rdp(points.view(), epsilon)));
+        assert!(mask[0] && mask[mask.len() - 1]);
+    }
+}
+
+#[test]
+fn imai_iri_3d() {
+    let t = ndarray::Array1::linspace(0.0, 6.0, 200);
+    let helix = ndarray::stack![Axis(1), t.mapv(f64::cos), t.mapv(f64::sin), t];
+    let mask = curved::imai_iri(helix.view(), 0.01);
+    assert!(count(&mask) <= count(&curved::rdp(helix.view(), 0.01)));
+}
+
+fn curve() -> impl Strategy<Value = Array2<f64>> {
+    (2usize..60, any::<bool>()).prop_flat_map(|(len, grid)| {
+        // Coordinates on a coarse grid make collinear and coincident points likely.
+        let coordinate = if grid { (-4i32..4).prop_map(f64::from).boxed() } else { (-100.0..100.0).boxed() };
+        proptest::collection::vec(coordinate, len * 2)
+            .prop_map(move |coordinates| Array2::from_shape_vec((len, 2), coordinates).unwrap())
+    })
+}
+
+proptest! {
+    #[test]
+    fn imai_iri_never_more_than_rdp(points in curve(), epsilon in 0.0..50.0f64) {
+        let mask = curved::imai_iri(points.view(), epsilon);
+        prop_assert!(mask[0] && mask[mask.len() - 1]);
+        prop_assert!(count(&mask) <= count(&curved::rdp(points.view(), epsilon)));
+
+        // The directions tracked in 2D must find the same optimum as checking every shortcut.
+        let embedded = ndarray::concatenate![Axis(1), points, Array2::zeros((points.nrows(), 1))];
+        prop_assert_eq!(count(&mask), count(&curved::imai_iri(embedded.view(), epsilon)));
+    }
+
+    #[test]
+    fn imai_iri_3d_never_more_than_rdp(
+        coordinates in proptest::collection::vec(-10.0..10.0f64, 6..90),
+        epsilon in 0.0..5.0f64
+    ) {
+        let len = coordinates.len() / 3;
+        let points = Array2::from_shape_vec((len, 3), coordinates[..len * 3].to_vec()).unwrap();
+        let mask = curved::imai_iri(points.view(), epsilon);
+        prop_assert!(count(&mask) <= count(&curved::rdp(points.view(), epsilon)));
+    }
+}
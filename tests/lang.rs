@@ -0,0 +1,29 @@
+mod common;
+
+use ndarray::{array, Array1, Axis};
+
+#[test]
+fn lang_keeps_corners() {
+    let points = array![[0.0, 0.0], [1.0, 0.0], [2.0, 0.05], [3.0, 0.0], [3.0, 1.0], [3.0, 2.0]];
+    assert_eq!(curved::lang(points.view(), 0.1, 8), array![true, false, false, true, false, true]);
+}
+
+#[test]
+fn lang_look_ahead_limits_spacing() {
+    let x = Array1::range(0.0, 10.0, 1.0);
+    let points = ndarray::stack![Axis(1), x.clone(), x];
+    let retained = |look_ahead| curved::lang(points.view(), 0.1, look_ahead)
+        .iter().enumerate().filter(|(_, &keep)| keep).map(|(i, _)| i).collect::<Vec<_>>();
+    assert_eq!(retained(20), vec![0, 9]);
+    assert_eq!(retained(4), vec![0, 4, 8, 9]);
+    assert_eq!(retained(1), (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn lang_within_tolerance() {
+    let points = include!("../fixtures/norway_main.rs");
+    let mask = curved::lang(points.view(), 0.05, 64);
+    let retained: Vec<usize> = mask.iter().enumerate().filter(|(_, &keep)| keep).map(|(i, _)| i).collect();
+    assert!(retained.windows(2).all(|pair| pair[1] - pair[0] <= 64));
+    common::assert_within_tolerance(points.view(), &retained, 0.05);
+}
@@ -0,0 +1,67 @@
+import threading
+import time
+import unittest
+
+import numpy as np
+
+import curved
+
+
+class TestGil(unittest.TestCase):
+    def test_rdp_releases_gil(self):
+        # A random walk large enough that simplifying it takes a noticeable amount of time.
+        rng = np.random.default_rng(1)
+        points = np.cumsum(rng.standard_normal((2_000_000, 2)), axis=0)
+
+        # Count how often another Python thread gets to run while curved is simplifying. If the
+        # GIL were held throughout, it would not get to run at all.
+        counter = 0
+        running = threading.Event()
+        stop = threading.Event()
+
+        def count():
+            nonlocal counter
+            running.set()
+            while not stop.is_set():
+                counter += 1
+
+        thread = threading.Thread(target=count)
+        thread.start()
+        running.wait()
+        try:
+            time.sleep(0.01)
+            before = counter
+            start = time.perf_counter()
+            curved.rdp(points, 0.1)
+            elapsed = time.perf_counter() - start
+            during = counter - before
+        finally:
+            stop.set()
+            thread.join()
+
+        self.assertGreater(elapsed, 0.05)
+        self.assertGreater(during, 1000)
+
+    def test_threads_match_serial(self):
+        # Concurrent calls must agree with serial ones. Any speedup is too noisy to assert on.
+        rng = np.random.default_rng(2)
+        curves = [np.cumsum(rng.standard_normal((1_000_000, 2)), axis=0) for _ in range(4)]
+        serial = [curved.rdp(points, 0.1) for points in curves]
+
+        results = [None] * len(curves)
+
+        def simplify(i):
+            results[i] = curved.rdp(curves[i], 0.1)
+
+        threads = [threading.Thread(target=simplify, args=(i,)) for i in range(len(curves))]
+        for thread in threads:
+            thread.start()
+        for thread in threads:
+            thread.join()
+
+        for expected, result in zip(serial, results):
+            np.testing.assert_array_equal(expected, result)
+
+
+if __name__ == '__main__':
+    unittest.main()
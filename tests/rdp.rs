@@ -1,4 +1,4 @@
-use ndarray::{array};
+use ndarray::{array, s, Array2};
 
 #[test]
 fn rdp_norway() {
@@ -6,4 +6,173 @@ fn rdp_norway() {
     let comparison = include!("../fixtures/norway_mask_0.0005.rs");
     let mask = curved::rdp(points.view(), 0.0005);
     assert_eq!(mask, comparison);
-}
\ No newline at end of file
+}
+
+#[test]
+fn rdp_count_norway() {
+    // Keeping as many points as the epsilon mask must select the same points.
+    let points = include!("../fixtures/norway_main.rs");
+    let comparison = include!("../fixtures/norway_mask_0.0005.rs");
+    let n_points = comparison.iter().filter(|&&keep| keep).count();
+    let mask = curved::rdp_count(points.view(), n_points);
+    assert_eq!(mask, comparison);
+}
+
+#[test]
+fn rdp_count_keeps_requested_points() {
+    let points = array![[0.0, 0.0], [1.0, 0.1], [2.0, -3.0], [3.0, 5.0], [4.0, 0.0]];
+    assert_eq!(curved::rdp_count(points.view(), 3), array![true, false, false, true, true]);
+    assert_eq!(curved::rdp_count(points.view(), 0), array![true, false, false, false, true]);
+    assert_eq!(curved::rdp_count(points.view(), 10), array![true, true, true, true, true]);
+}
+
+#[test]
+fn rdp_importance_norway() {
+    let points = include!("../fixtures/norway_main.rs");
+    let importance = curved::rdp_importance(points.view());
+    for &epsilon in [0.0005, 0.001, 0.01, 0.1].iter() {
+        assert_eq!(importance.mapv(|i| i > epsilon), curved::rdp(points.view(), epsilon));
+    }
+}
+
+#[test]
+fn rdp_deep_splits() {
+    // A long straight run followed by a zigzag of decreasing amplitude, where every range of
+    // the zigzag splits at its second point. The number of nested splits grows with the zigzag
+    // length, which would overflow the small stack below if they were made recursively. The
+    // work grows with its square though, so the zigzag is kept to a fraction of the points.
+    let (n_straight, n_zigzag) = (997_000, 3_000);
+    let mut points = Array2::zeros((n_straight + n_zigzag, 2));
+    for i in 0..n_straight {
+        points[[i, 0]] = (i as f64 - n_straight as f64) / n_straight as f64;
+    }
+    for j in 1..=n_zigzag {
+        let amplitude = 1.0 + (n_zigzag - j) as f64 / n_zigzag as f64;
+        points[[n_straight + j - 1, 0]] = j as f64;
+        points[[n_straight + j - 1, 1]] = if j % 2 == 0 { amplitude } else { -amplitude };
+    }
+
+    let mask = std::thread::Builder::new()
+        .stack_size(256 * 1024)
+        .spawn(move || curved::rdp(points.view(), 0.01))
+        .unwrap()
+        .join()
+        .unwrap();
+
+    assert!(mask.slice(s![n_straight..]).iter().all(|&keep| keep));
+    assert_eq!(mask.slice(s![1..n_straight - 1]).iter().filter(|&&keep| keep).count(), 0);
+}
+
+#[test]
+fn try_rdp_errors() {
+    use curved::CurvedError;
+
+    let points = array![[0.0, 0.0], [1.0, 1.0], [2.0, 0.0]];
+    assert_eq!(curved::try_rdp(points.view(), 0.5), Ok(array![true, true, true]));
+    assert_eq!(curved::try_rdp(Array2::zeros((0, 2)).view(), 0.5), Err(CurvedError::EmptyInput));
+    assert_eq!(curved::try_rdp(Array2::zeros((3, 0)).view(), 0.5), Err(CurvedError::ZeroDimensional));
+    assert_eq!(
+        curved::try_rdp(array![[0.0, 0.0], [1.0, f64::NAN]].view(), 0.5),
+        Err(CurvedError::NonFiniteCoordinate(1))
+    );
+    assert_eq!(curved::try_rdp(points.view(), -1.0), Err(CurvedError::InvalidEpsilon(-1.0)));
+    assert!(curved::try_rdp(points.view(), f64::NAN).is_err());
+}
+
+#[test]
+fn rdp_coincident_endpoints() {
+    // The furthest point from the shared endpoint is retained, rather than nothing.
+    let points = array![[0.0, 0.0], [1.0, 0.1], [2.0, 0.0], [1.0, -0.1], [0.0, 0.0]];
+    assert_eq!(curved::rdp(points.view(), 0.5), array![true, false, true, false, true]);
+}
+
+#[test]
+fn rdp_closed_ring() {
+    let options = curved::RdpOptions { closed: true, ..Default::default() };
+
+    // A square with extra points along its sides simplifies to its corners.
+    let square = array![
+        [0.0, 0.0], [1.0, 0.0], [2.0, 0.0], [2.0, 1.0], [2.0, 2.0],
+        [1.0, 2.0], [0.0, 2.0], [0.0, 1.0], [0.0, 0.0]
+    ];
+    let mask = curved::rdp_with_options(square.view(), 0.1, &options);
+    assert_eq!(mask, array![true, false, true, false, true, false, true, false, true]);
+
+    // The closing point is implied if it isn't repeated.
+    let mask = curved::rdp_with_options(square.slice(s![..-1, ..]), 0.1, &options);
+    assert_eq!(mask, array![true, false, true, false, true, false, true, false]);
+
+    // A large tolerance still leaves a valid ring.
+    let mask = curved::rdp_with_options(square.view(), 10.0, &options);
+    assert_eq!(mask.iter().filter(|&&keep| keep).count(), 4);
+}
+
+#[test]
+fn rdp_segment_metric() {
+    // The tip of the hairpin projects beyond the end of the line, so it is only retained when
+    // measuring to the segment.
+    let points = array![[0.0, 0.0], [2.0, 0.0], [3.0, 0.1], [2.0, 0.2], [1.0, 0.2]];
+    let line = curved::RdpOptions::default();
+    let segment = curved::RdpOptions { metric: curved::DistanceMetric::Segment, ..Default::default() };
+    assert_eq!(curved::rdp_with_options(points.view(), 0.5, &line), array![true, false, false, false, true]);
+    assert_eq!(curved::rdp_with_options(points.view(), 0.5, &segment), array![true, false, true, false, true]);
+}
+
+#[test]
+fn rdp_weights() {
+    // Weighting a coordinate is the same as scaling it before simplifying.
+    let points = include!("../fixtures/norway_main.rs");
+    let mut scaled = points.clone();
+    scaled.column_mut(1).mapv_inplace(|y| y * 4.0);
+    let weighted = curved::RdpOptions { weights: Some(vec![1.0, 4.0]), ..Default::default() };
+    assert_eq!(curved::rdp_with_options(points.view(), 0.0005, &weighted), curved::rdp(scaled.view(), 0.0005));
+    assert_ne!(curved::rdp_with_options(points.view(), 0.0005, &weighted), curved::rdp(points.view(), 0.0005));
+
+    let segment = curved::RdpOptions { metric: curved::DistanceMetric::Segment, ..weighted.clone() };
+    let scaled_segment = curved::RdpOptions { metric: curved::DistanceMetric::Segment, ..Default::default() };
+    assert_eq!(
+        curved::rdp_with_options(points.view(), 0.0005, &segment),
+        curved::rdp_with_options(scaled.view(), 0.0005, &scaled_segment));
+
+    for weights in [vec![1.0], vec![1.0, -1.0], vec![1.0, f64::NAN]].iter() {
+        let options = curved::RdpOptions { weights: Some(weights.clone()), ..Default::default() };
+        assert_eq!(curved::try_rdp_with_options(points.view(), 0.0005, &options), Err(curved::CurvedError::InvalidWeights));
+    }
+    let geographic = curved::RdpOptions {
+        metric: curved::DistanceMetric::Geographic(curved::Ellipsoid::Sphere),
+        ..weighted
+    };
+    assert_eq!(curved::try_rdp_with_options(points.view(), 100.0, &geographic), Err(curved::CurvedError::InvalidWeights));
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_rdp_matches_serial() {
+    use ndarray::Axis;
+    use ndarray_rand::{RandomExt, rand::{SeedableRng, rngs::StdRng}, rand_distr::StandardNormal};
+
+    let points = include!("../fixtures/norway_main.rs");
+    assert_eq!(curved::par_rdp(points.view(), 0.0005), curved::rdp(points.view(), 0.0005));
+
+    // A random walk, large enough for the distances to be calculated in parallel too.
+    let mut points: Array2<f64> = Array2::random_using((100_000, 3), StandardNormal, &mut StdRng::seed_from_u64(1));
+    points.accumulate_axis_inplace(Axis(0), |&prev, curr| *curr += prev);
+    for &closed in [false, true].iter() {
+        let serial = curved::RdpOptions { closed, ..Default::default() };
+        let parallel = curved::RdpOptions { closed, parallel: true, ..Default::default() };
+        assert_eq!(
+            curved::rdp_with_options(points.view(), 1.0, &parallel),
+            curved::rdp_with_options(points.view(), 1.0, &serial)
+        );
+    }
+}
+
+#[test]
+fn rdp_f32() {
+    let points = array![[0.0f32, 0.0], [1.0, 0.1], [2.0, -3.0], [3.0, 5.0], [4.0, 0.0]];
+    let expected = curved::rdp(points.mapv(f64::from).view(), 1.0);
+    assert_eq!(curved::rdp(points.view(), 1.0f32), expected);
+    assert_eq!(curved::try_rdp(points.view(), 1.0f32), Ok(expected));
+    assert_eq!(curved::rdp_count(points.view(), 3), array![true, false, false, true, true]);
+    assert_eq!(curved::rdp_importance(points.view())[0], f32::INFINITY);
+}
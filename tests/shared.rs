@@ -0,0 +1,107 @@
+use std::collections::BTreeSet;
+
+use ndarray::{array, s, Array1, Array2, Axis};
+
+/// Two squares sharing a wiggly border, and an island.
+fn regions() -> (Array2<f64>, Vec<usize>) {
+    let y: Array1<f64> = Array1::linspace(0.0, 10.0, 41);
+    let border = ndarray::stack![Axis(1), y.mapv(|y| 5.0 + 0.3 * (y * 2.0).sin()), y];
+    let left = ndarray::concatenate![
+        Axis(0), array![[0.0, 0.0]], border, array![[0.0, 10.0], [0.0, 0.0]]
+    ];
+    let right = ndarray::concatenate![
+        Axis(0), array![[10.0, 0.0], [10.0, 10.0]], border.slice(s![..;-1, ..]), array![[10.0, 0.0]]
+    ];
+    let island = array![[20.0, 0.0], [21.0, 0.1], [22.0, 0.0], [22.0, 2.0], [20.0, 2.0]];
+    let offsets = vec![0, left.nrows(), left.nrows() + right.nrows(), left.nrows() + right.nrows() + island.nrows()];
+    (ndarray::concatenate![Axis(0), left, right, island], offsets)
+}
+
+/// The coordinates of the retained points of a part with an x coordinate strictly between 4
+/// and 6, which are on the border.
+fn border(points: &Array2<f64>, mask: &Array1<bool>, start: usize, end: usize) -> BTreeSet<(u64, u64)> {
+    (start..end)
+        .filter(|&i| mask[i] && points[[i, 0]] > 4.0 && points[[i, 0]] < 6.0)
+        .map(|i| (points[[i, 0]].to_bits(), points[[i, 1]].to_bits()))
+        .collect()
+}
+
+#[test]
+fn rdp_shared_watertight() {
+    let (points, offsets) = regions();
+    let options = curved::RdpOptions::default();
+    let mask = curved::rdp_shared(points.view(), &offsets, 0.2, &options);
+    let left = border(&points, &mask, offsets[0], offsets[1]);
+    assert!(!left.is_empty());
+    assert_eq!(left, border(&points, &mask, offsets[1], offsets[2]));
+
+    // The corners are junctions, so are always retained, as are repeated first points.
+    for &i in [0, offsets[1] - 1, offsets[1], offsets[1] + 1, offsets[2] - 1].iter() {
+        assert!(mask[i]);
+    }
+
+    // The island is simplified as a closed ring.
+    let closed = curved::RdpOptions { closed: true, ..Default::default() };
+    assert_eq!(
+        mask.slice(s![offsets[2]..]),
+        curved::rdp_with_options(points.slice(s![offsets[2].., ..]), 0.2, &closed)
+    );
+
+    // Simplifying each ring independently doesn't keep the border consistent.
+    let independent = curved::rdp_batch(points.view(), &offsets, 0.2, &closed);
+    assert_ne!(
+        border(&points, &independent, offsets[0], offsets[1]),
+        border(&points, &independent, offsets[1], offsets[2])
+    );
+}
+
+#[test]
+fn rdp_shared_identical_rings() {
+    // A hole and the island filling it, in opposite directions and starting at different points.
+    let ring: Array2<f64> = array![[0.0, 0.0], [1.0, 0.1], [2.0, 0.0], [2.1, 1.0], [2.0, 2.0], [0.0, 2.0]];
+    let reversed = ring.slice(s![..;-1, ..]).to_owned();
+    let rotated = ndarray::concatenate![Axis(0), reversed.slice(s![2.., ..]), reversed.slice(s![..2, ..])];
+    let points = ndarray::concatenate![Axis(0), ring, rotated];
+    let mask = curved::rdp_shared(points.view(), &[0, 6, 12], 0.2, &curved::RdpOptions::default());
+    let retained = |start: usize| (start..start + 6)
+        .filter(|&i| mask[i])
+        .map(|i| (points[[i, 0]].to_bits(), points[[i, 1]].to_bits()))
+        .collect::<BTreeSet<_>>();
+    assert_eq!(retained(0), retained(6));
+    assert!(retained(0).len() >= 3);
+}
+
+#[test]
+fn rdp_shared_keeps_valid_rings() {
+    // Two unit squares sharing an edge, simplified with a tolerance larger than either.
+    let points: Array2<f64> = array![
+        [0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0], [0.0, 0.0],
+        [1.0, 0.0], [2.0, 0.0], [2.0, 1.0], [1.0, 1.0], [1.0, 0.0]
+    ];
+    let mask = curved::rdp_shared(points.view(), &[0, 5, 10], 5.0, &curved::RdpOptions::default());
+    for &(start, end) in [(0, 5), (5, 10)].iter() {
+        let retained = (start..end)
+            .filter(|&i| mask[i])
+            .map(|i| (points[[i, 0]].to_bits(), points[[i, 1]].to_bits()))
+            .collect::<BTreeSet<_>>();
+        assert!(retained.len() >= 3);
+        assert_eq!(mask[start], mask[end - 1]);
+    }
+
+    // The shared edge is retained on both sides.
+    assert!(mask[1] && mask[2] && mask[5] && mask[8]);
+}
+
+#[test]
+fn try_rdp_shared_errors() {
+    let points = array![[0.0, 0.0], [1.0, 1.0], [2.0, 0.0]];
+    let options = curved::RdpOptions::default();
+    assert_eq!(
+        curved::try_rdp_shared(points.view(), &[0, 2], 0.5, &options),
+        Err(curved::CurvedError::InvalidOffsets)
+    );
+    assert_eq!(
+        curved::try_rdp_shared(points.view(), &[0, 3], -1.0, &options),
+        Err(curved::CurvedError::InvalidEpsilon(-1.0))
+    );
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use ndarray::{array, Array1, Axis};
+
+use curved::{CurvedError, StreamingSimplifier};
+
+#[test]
+fn streaming_within_tolerance() {
+    let points = include!("../fixtures/norway_main.rs");
+    let epsilon: f64 = 0.05;
+    let mut simplifier = StreamingSimplifier::new(epsilon);
+    for point in points.outer_iter() {
+        simplifier.push(point).unwrap();
+    }
+    simplifier.flush();
+    let retained: Vec<usize> = simplifier.map(|(i, _)| i).collect();
+    common::assert_within_tolerance(points.view(), &retained, epsilon);
+}
+
+#[test]
+fn streaming_window_is_bounded() {
+    let x = Array1::range(0.0, 10.0, 1.0);
+    let points = ndarray::stack![Axis(1), x.clone(), x];
+
+    let mut simplifier = StreamingSimplifier::new(0.1);
+    for point in points.outer_iter() {
+        simplifier.push(point).unwrap();
+    }
+    // Only the first point is final until the curve is flushed.
+    assert_eq!(simplifier.next(), Some((0, array![0.0, 0.0])));
+    assert_eq!(simplifier.next(), None);
+    simplifier.flush();
+    assert_eq!(simplifier.next(), Some((9, array![9.0, 9.0])));
+
+    let mut simplifier = StreamingSimplifier::with_max_window(0.1, 4);
+    for point in points.outer_iter() {
+        simplifier.push(point).unwrap();
+    }
+    simplifier.flush();
+    assert_eq!(simplifier.map(|(i, _)| i).collect::<Vec<_>>(), vec![0, 3, 6, 9]);
+}
+
+#[test]
+fn streaming_errors() {
+    let mut simplifier = StreamingSimplifier::new(0.1f32);
+    assert_eq!(simplifier.push(array![].view()), Err(CurvedError::ZeroDimensional));
+    simplifier.push(array![0.0, 0.0].view()).unwrap();
+    assert_eq!(simplifier.push(array![1.0].view()), Err(CurvedError::DimensionMismatch(2)));
+    assert_eq!(simplifier.push(array![1.0, f32::NAN].view()), Err(CurvedError::NonFiniteCoordinate(1)));
+    simplifier.push(array![1.0, 0.0].view()).unwrap();
+
+    // After a flush, a new curve can have a different number of dimensions.
+    simplifier.flush();
+    simplifier.push(array![1.0, 0.0, 0.0].view()).unwrap();
+    assert_eq!(simplifier.map(|(i, _)| i).collect::<Vec<_>>(), vec![0, 1, 2]);
+}
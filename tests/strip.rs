@@ -0,0 +1,67 @@
+use ndarray::{array, Array1, Array2, Axis};
+
+#[test]
+fn reumann_witkam_follows_strip() {
+    let points = array![[0.0, 0.0], [1.0, 0.0], [2.0, 0.05], [3.0, 0.0], [3.0, 1.0], [3.0, 2.0]];
+    let mask = curved::reumann_witkam(points.view(), 0.1);
+    assert_eq!(mask, array![true, false, false, true, false, true]);
+}
+
+#[test]
+fn opheim_skips_points_near_key() {
+    // The first point is within the tolerance of the key, so Opheim ignores its direction,
+    // whereas Reumann-Witkam lays the strip along it.
+    let points = array![[0.0, 0.0], [0.05, 0.05], [1.0, 0.0], [2.0, 0.0]];
+    assert_eq!(curved::opheim(points.view(), 0.1, 10.0), array![true, false, false, true]);
+    assert_eq!(curved::reumann_witkam(points.view(), 0.1), array![true, true, false, true]);
+}
+
+#[test]
+fn opheim_limits_strip_length() {
+    let x = Array1::range(0.0, 10.0, 1.0);
+    let points = ndarray::stack![Axis(1), x, Array1::zeros(10)];
+    let mut expected = Array1::from_elem(10, false);
+    for &i in [0, 3, 6, 9].iter() {
+        expected[i] = true;
+    }
+    assert_eq!(curved::opheim(points.view(), 0.1, 3.5), expected);
+    assert_eq!(curved::reumann_witkam(points.view(), 0.1).iter().filter(|&&keep| keep).count(), 2);
+}
+
+#[test]
+fn strip_short_curves() {
+    let empty = Array2::<f64>::zeros((0, 2));
+    assert_eq!(curved::reumann_witkam(empty.view(), 0.1).len(), 0);
+    assert_eq!(curved::opheim(array![[1.0f32, 2.0, 3.0]].view(), 0.1, 1.0), array![true]);
+    assert_eq!(curved::reumann_witkam(array![[0.0, 0.0], [0.0, 0.0]].view(), 0.1), array![true, true]);
+}
+
+#[test]
+fn strip_norway() {
+    // Both retain a similar number of points to RDP with the same tolerance.
+    let points = include!("../fixtures/norway_main.rs");
+    let rdp = curved::rdp(points.view(), 0.05).iter().filter(|&&keep| keep).count();
+    for mask in [
+        curved::reumann_witkam(points.view(), 0.05),
+        curved::opheim(points.view(), 0.05, 1.0),
+    ].iter() {
+        let retained = mask.iter().filter(|&&keep| keep).count();
+        assert!(retained > rdp / 2 && retained < rdp * 2);
+        assert!(mask[0] && mask[mask.len() - 1]);
+    }
+}
+
+#[test]
+fn try_opheim_errors() {
+    use curved::CurvedError;
+
+    let points = array![[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]];
+    assert_eq!(curved::try_opheim(points.view(), 0.1, 1.0), Ok(curved::opheim(points.view(), 0.1, 1.0)));
+    assert_eq!(curved::try_opheim(points.view(), -0.1, 1.0), Err(CurvedError::InvalidEpsilon(-0.1)));
+    assert_eq!(curved::try_opheim(points.view(), 0.1, -1.0), Err(CurvedError::InvalidMaxDistance(-1.0)));
+    assert!(matches!(
+        curved::try_opheim(points.view(), 0.1, f64::NAN),
+        Err(CurvedError::InvalidMaxDistance(distance)) if distance.is_nan()
+    ));
+    assert_eq!(curved::try_opheim(Array2::<f64>::zeros((0, 2)).view(), 0.1, 1.0), Err(CurvedError::EmptyInput));
+}
@@ -0,0 +1,71 @@
+use ndarray::{array, Array1, Array2, ArrayView2};
+
+/// Counts the pairs of segments of the retained points that cross, other than neighbours
+/// meeting at their shared point.
+fn crossings(points: ArrayView2<'_, f64>, mask: &Array1<bool>) -> usize {
+    let retained: Vec<[f64; 2]> = points.outer_iter().zip(mask.iter())
+        .filter(|(_, &keep)| keep)
+        .map(|(point, _)| [point[0], point[1]])
+        .collect();
+    let orientation = |a: [f64; 2], b: [f64; 2], c: [f64; 2]| (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0]);
+    let mut count = 0;
+    for i in 0..retained.len() - 1 {
+        for j in i + 2..retained.len() - 1 {
+            let (a, b, c, d) = (retained[i], retained[i + 1], retained[j], retained[j + 1]);
+            if a == d || b == c {
+                continue;
+            }
+            if orientation(a, b, c) * orientation(a, b, d) < 0.0 && orientation(c, d, a) * orientation(c, d, b) < 0.0 {
+                count += 1;
+            }
+        }
+    }
+    count
+}
+
+#[test]
+fn preserve_topology_norway() {
+    let points = include!("../fixtures/norway_main.rs");
+    let options = curved::RdpOptions { preserve_topology: true, ..Default::default() };
+    let mut crossed = false;
+    for &epsilon in [0.01, 0.05, 0.1, 0.2, 0.5].iter() {
+        let simple = curved::rdp(points.view(), epsilon);
+        crossed |= crossings(points.view(), &simple) > 0;
+
+        let mask = curved::rdp_with_options(points.view(), epsilon, &options);
+        assert_eq!(crossings(points.view(), &mask), 0);
+
+        // Only points are added to the plain RDP result.
+        assert!(simple.iter().zip(mask.iter()).all(|(&simple, &keep)| !simple || keep));
+    }
+    assert!(crossed);
+}
+
+#[test]
+fn preserve_topology_closed() {
+    // The segment closing the simplified ring crosses the first segment.
+    let points: Array2<f64> = array![
+        [2.0, 0.0], [4.0, 4.0], [0.0, 1.0], [-5.0, 5.0], [-3.0, 0.0], [-1.0, -1.0], [0.0, -2.0], [3.0, -3.0]
+    ];
+    let mut ring = points.clone();
+    ring.push_row(points.row(0)).unwrap();
+    let ring_crossings = |mask: Array1<bool>| {
+        let mut mask = mask.to_vec();
+        mask.push(true);
+        crossings(ring.view(), &Array1::from(mask))
+    };
+
+    let closed = curved::RdpOptions { closed: true, ..Default::default() };
+    assert!(ring_crossings(curved::rdp_with_options(points.view(), 3.0, &closed)) > 0);
+    let options = curved::RdpOptions { preserve_topology: true, ..closed };
+    assert_eq!(ring_crossings(curved::rdp_with_options(points.view(), 3.0, &options)), 0);
+}
+
+#[test]
+fn preserve_topology_errors() {
+    let options = curved::RdpOptions { preserve_topology: true, ..Default::default() };
+    assert_eq!(
+        curved::try_rdp_with_options(array![[0.0], [1.0]].view(), 0.1, &options),
+        Err(curved::CurvedError::TooFewDimensions(2))
+    );
+}
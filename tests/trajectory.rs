@@ -0,0 +1,41 @@
+use ndarray::{array, s};
+
+use curved::{DistanceMetric, RdpOptions};
+
+#[test]
+fn rdp_synchronized_keeps_stops() {
+    // A vehicle driving along a straight road, which stops for a while along the way.
+    let track = array![
+        [0.0, 0.0, 0.0], [1.0, 1.0, 0.0], [2.0, 1.0, 0.0], [3.0, 1.0, 0.0],
+        [4.0, 2.0, 0.0], [5.0, 3.0, 0.0], [6.0, 4.0, 0.0]
+    ];
+
+    // Spatially every point lies on the road, so only the endpoints are needed.
+    let mask = curved::rdp(track.slice(s![.., 1..]), 0.1);
+    assert_eq!(mask, array![true, false, false, false, false, false, true]);
+
+    // The stop is retained when the positions are compared at the same time.
+    let options = RdpOptions { metric: DistanceMetric::Synchronized { time: 0 }, ..Default::default() };
+    let mask = curved::rdp_with_options(track.view(), 0.1, &options);
+    assert_eq!(mask, array![true, true, false, true, false, false, true]);
+}
+
+#[test]
+fn rdp_synchronized_time_column() {
+    let track = array![[0.0, 0.0, 0.0], [0.0, 1.0, 0.5], [0.0, 2.0, 2.0]];
+    let options = RdpOptions { metric: DistanceMetric::Synchronized { time: 2 }, ..Default::default() };
+    assert_eq!(curved::rdp_with_options(track.view(), 0.4, &options), array![true, true, true]);
+    assert_eq!(curved::rdp_with_options(track.view(), 0.6, &options), array![true, false, true]);
+
+    let options = RdpOptions { metric: DistanceMetric::Synchronized { time: 3 }, ..Default::default() };
+    assert_eq!(
+        curved::try_rdp_with_options(track.view(), 0.4, &options),
+        Err(curved::CurvedError::TooFewDimensions(4))
+    );
+
+    let options = RdpOptions { metric: DistanceMetric::Synchronized { time: usize::MAX }, ..Default::default() };
+    assert_eq!(
+        curved::try_rdp_with_options(track.view(), 0.4, &options),
+        Err(curved::CurvedError::TooFewDimensions(usize::MAX))
+    );
+}
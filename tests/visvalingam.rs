@@ -0,0 +1,36 @@
+use ndarray::{array, Array1, Array2, Axis};
+
+#[test]
+fn visvalingam_removes_collinear_points() {
+    let points = array![[0.0, 0.0], [1.0, 0.0], [2.0, 0.0], [3.0, 1.0], [4.0, 1.0]];
+    let mask = curved::visvalingam(points.view(), 0.1);
+    assert_eq!(mask, array![true, false, true, true, true]);
+}
+
+#[test]
+fn visvalingam_keeps_endpoints_only_for_large_area() {
+    let points = array![[0.0, 0.0], [1.0, 1.0], [2.0, -1.0], [3.0, 0.0]];
+    let mask = curved::visvalingam(points.view(), 100.0);
+    assert_eq!(mask, array![true, false, false, true]);
+}
+
+#[test]
+fn visvalingam_matches_2d_in_3d() {
+    // Embedding a 2D curve in a plane in 3D must not change the result.
+    let t = Array1::linspace(0.0, 6.0, 200);
+    let planar = ndarray::stack![Axis(1), t.mapv(f64::cos), t.mapv(f64::sin)];
+    let embedded: Array2<f64> = ndarray::concatenate![Axis(1), planar, Array2::zeros((200, 1))];
+    assert_eq!(
+        curved::visvalingam(planar.view(), 0.001),
+        curved::visvalingam(embedded.view(), 0.001)
+    );
+}
+
+#[test]
+fn visvalingam_f32_matches_f64() {
+    let points: Array2<f64> = array![[0.0, 0.0], [1.0, 0.0], [2.0, 0.0], [3.0, 1.0], [4.0, 1.0], [5.0, 3.0], [6.0, 0.0]];
+    assert_eq!(
+        curved::visvalingam(points.mapv(|v| v as f32).view(), 0.6),
+        curved::visvalingam(points.view(), 0.6)
+    );
+}